@@ -101,7 +101,7 @@ impl State for GameState {
     }
 
     fn event(&mut self, _: &mut Context, event: Event) -> tetra::Result {
-        if let Event::Resized { width, height } = event {
+        if let Event::Resized { width, height, .. } = event {
             self.scaler.set_outer_size(width, height);
         }
 
@@ -0,0 +1,129 @@
+// This example demonstrates how to composite multiple canvases together,
+// in order to create a simple 2D lighting effect.
+//
+// The scene is rendered to one canvas, and the lights are rendered to another
+// (the 'light map'), using the render target stack. The scene is then drawn to
+// the screen through a shader, which multiplies it by the light map - areas that
+// aren't lit will be darkened, while areas that are lit will show the scene as
+// normal.
+
+use tetra::graphics::{self, BlendState, Canvas, Color, DrawMode, DrawParams, Shader, Texture};
+use tetra::input;
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, State};
+
+const SCREEN_WIDTH: i32 = 640;
+const SCREEN_HEIGHT: i32 = 480;
+
+const AMBIENT_LIGHT: Color = Color::rgb(0.1, 0.1, 0.2);
+
+struct Light {
+    position: Vec2<f32>,
+    radius: f32,
+    color: Color,
+}
+
+struct GameState {
+    scene: Canvas,
+    light_map: Canvas,
+    composite: Shader,
+    backdrop: Texture,
+    lights: Vec<Light>,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        Ok(GameState {
+            scene: Canvas::new(ctx, SCREEN_WIDTH, SCREEN_HEIGHT)?,
+            light_map: Canvas::new(ctx, SCREEN_WIDTH, SCREEN_HEIGHT)?,
+            composite: Shader::from_fragment_file(ctx, "./examples/resources/lighting.frag")?,
+            backdrop: Texture::new(ctx, "./examples/resources/backdrop.png")?,
+            lights: vec![
+                Light {
+                    position: Vec2::new(160.0, 240.0),
+                    radius: 96.0,
+                    color: Color::rgb(1.0, 0.6, 0.3),
+                },
+                Light {
+                    position: Vec2::new(480.0, 160.0),
+                    radius: 128.0,
+                    color: Color::rgb(0.4, 0.6, 1.0),
+                },
+                Light {
+                    position: Vec2::zero(),
+                    radius: 112.0,
+                    color: Color::WHITE,
+                },
+            ],
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        // The last light follows the mouse.
+        if let Some(light) = self.lights.last_mut() {
+            light.position = input::get_mouse_position(ctx).round();
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        // First, draw the scene.
+        graphics::push_canvas(ctx, &self.scene);
+        graphics::clear(ctx, Color::BLACK);
+
+        self.backdrop.draw(
+            ctx,
+            DrawParams::new().scale(Vec2::new(
+                SCREEN_WIDTH as f32 / self.backdrop.width() as f32,
+                SCREEN_HEIGHT as f32 / self.backdrop.height() as f32,
+            )),
+        );
+
+        graphics::pop_canvas(ctx);
+
+        // Next, draw the lights. They're drawn with additive blending, so that
+        // overlapping lights get brighter.
+        graphics::push_canvas(ctx, &self.light_map);
+        graphics::clear(ctx, AMBIENT_LIGHT);
+        graphics::set_blend_state(ctx, BlendState::add(false));
+
+        for light in &self.lights {
+            // A few layers of circles gives a cheap approximation of falloff.
+            for i in 1..=4 {
+                let scale = i as f32 / 4.0;
+
+                graphics::draw_circle(
+                    ctx,
+                    DrawMode::Fill,
+                    light.position,
+                    light.radius * scale,
+                    light.color.with_alpha(0.25),
+                );
+            }
+        }
+
+        graphics::reset_blend_state(ctx);
+        graphics::pop_canvas(ctx);
+
+        // Finally, composite the two canvases on the screen. The scene is passed
+        // in as the main texture, and the light map as an extra uniform.
+        graphics::clear(ctx, Color::BLACK);
+
+        self.composite
+            .set_uniform(ctx, "u_light_map", self.light_map.texture());
+
+        graphics::apply_post_process(ctx, &self.scene, &self.composite);
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("2D Lighting", SCREEN_WIDTH, SCREEN_HEIGHT)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -0,0 +1,16 @@
+#version 150
+
+in vec2 v_uv;
+in vec4 v_color;
+
+uniform sampler2D u_texture;
+uniform sampler2D u_light_map;
+
+out vec4 o_color;
+
+void main() {
+    vec4 scene = texture(u_texture, v_uv);
+    vec4 light = texture(u_light_map, v_uv);
+
+    o_color = v_color * vec4(scene.rgb * light.rgb, scene.a);
+}
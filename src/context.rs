@@ -2,11 +2,12 @@ use std::result;
 use std::thread;
 use std::time::{Duration, Instant};
 
-use crate::graphics::{self, GraphicsContext};
+use crate::graphics::scaling::{ScalingMode, ScreenScaler};
+use crate::graphics::{self, GlProfile, GraphicsContext};
 use crate::input::{self, InputContext};
 use crate::platform::{self, GraphicsDevice, Window};
 use crate::time::{self, TimeContext, Timestep};
-use crate::{Result, State, TetraError};
+use crate::{EventMode, Result, State, TetraError};
 
 #[cfg(feature = "audio")]
 use crate::audio::AudioDevice;
@@ -22,8 +23,12 @@ pub struct Context {
     pub(crate) time: TimeContext,
 
     pub(crate) running: bool,
+    pub(crate) quit_immediately: bool,
     pub(crate) quit_on_escape: bool,
+    pub(crate) quit_on_close: bool,
     pub(crate) fps_limit: bool,
+    pub(crate) event_mode: EventMode,
+    pub(crate) screen_scaler: Option<ScreenScaler>,
 }
 
 impl Context {
@@ -42,13 +47,19 @@ impl Context {
             println!("OpenGL Renderer: {}", device_info.renderer);
             println!("OpenGL Version: {}", device_info.opengl_version);
             println!("GLSL Version: {}", device_info.glsl_version);
+            println!("SDL Version: {}", window.get_sdl_version());
         }
 
-        let graphics = GraphicsContext::new(&mut device, window_width, window_height)?;
+        let graphics = GraphicsContext::new(
+            &mut device,
+            window_width,
+            window_height,
+            settings.generate_mipmaps,
+        )?;
         let input = InputContext::new();
         let time = TimeContext::new(settings.timestep);
 
-        Ok(Context {
+        let mut ctx = Context {
             window,
             device,
 
@@ -59,10 +70,29 @@ impl Context {
             time,
 
             running: false,
+            quit_immediately: false,
             quit_on_escape: settings.quit_on_escape,
+            quit_on_close: settings.quit_on_close,
+
+            fps_limit: settings.fps_limit,
+            event_mode: settings.event_mode,
+            screen_scaler: None,
+        };
+
+        if settings.auto_scale {
+            let (logical_width, logical_height) = settings
+                .logical_size
+                .unwrap_or((settings.window_width, settings.window_height));
+
+            ctx.screen_scaler = Some(ScreenScaler::with_window_size(
+                &mut ctx,
+                logical_width,
+                logical_height,
+                settings.scaling_mode,
+            )?);
+        }
 
-            fps_limit: settings.fps_limit
-        })
+        Ok(ctx)
     }
 
     /// Runs the game.
@@ -82,6 +112,19 @@ impl Context {
     /// or [`event`](State::event), the game will stop running and this method will
     /// return the error.
     ///
+    /// # Quitting
+    ///
+    /// The game will stop running when [`window::quit`](crate::window::quit) is called, or
+    /// when the user closes the window (unless [`ContextBuilder::quit_on_close`] has been
+    /// disabled). This is a graceful shutdown: the current cycle of the game loop will
+    /// finish (including any remaining updates, the call to [`draw`](State::draw) and
+    /// presenting to the screen), and then [`on_exit`](State::on_exit) will be called.
+    ///
+    /// If you need to stop the game without doing any further work, call
+    /// [`window::quit_immediately`](crate::window::quit_immediately) instead. The game loop
+    /// will stop as soon as the current `State` method returns, and `on_exit` will not
+    /// be called.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -117,15 +160,21 @@ impl Context {
         time::reset(self);
 
         self.running = true;
+        self.quit_immediately = false;
         self.window.set_visible(true);
 
-        let mut output = Ok(());
+        let mut output = self.game_loop(state);
 
-        if let Err(e) = self.game_loop(state) {
-            output = Err(e);
+        self.running = false;
+
+        if !self.quit_immediately {
+            let exit_output = state.on_exit(self);
+
+            if output.is_ok() {
+                output = exit_output;
+            }
         }
 
-        self.running = false;
         self.window.set_visible(false);
 
         output
@@ -138,14 +187,29 @@ impl Context {
     {
         let mut last_time = Instant::now();
 
+        // Whether events have been received that no update has seen yet. While this is
+        // true, we don't wait for new events, so that the game can respond as soon as
+        // enough time has passed for the next tick.
+        let mut unhandled_events = false;
+
         while self.running {
+            // Events are handled before the time is sampled, so that any time spent
+            // waiting for events is counted towards this frame rather than the next.
+            let received_events = platform::handle_events(self, state, !unhandled_events)?;
+            unhandled_events |= received_events;
+
             let curr_time = Instant::now();
             let diff_time = curr_time - last_time;
             last_time = curr_time;
 
             self.time.fps_tracker.push(diff_time);
+            self.time.elapsed_time += diff_time;
 
-            platform::handle_events(self, state)?;
+            if self.quit_immediately {
+                break;
+            }
+
+            self.time.updates = 0;
 
             match self.time.tick_rate {
                 Some(tick_rate) => {
@@ -156,6 +220,12 @@ impl Context {
                         state.update(self)?;
                         input::clear(self);
 
+                        self.time.updates += 1;
+
+                        if self.quit_immediately {
+                            return Ok(());
+                        }
+
                         self.time.accumulator -= tick_rate;
                     }
 
@@ -167,11 +237,27 @@ impl Context {
 
                     state.update(self)?;
                     input::clear(self);
+
+                    self.time.updates += 1;
+
+                    if self.quit_immediately {
+                        break;
+                    }
                 }
             }
 
+            if self.time.updates > 0 {
+                unhandled_events = false;
+            }
+
+            graphics::scaling::begin_auto_scale(self);
             state.draw(self)?;
 
+            if self.quit_immediately {
+                break;
+            }
+
+            graphics::scaling::end_auto_scale(self);
             graphics::present(self);
 
             // This provides a sensible FPS limit when running without vsync, and
@@ -214,6 +300,11 @@ pub struct ContextBuilder {
     pub(crate) resizable: bool,
     pub(crate) borderless: bool,
     pub(crate) multisampling: u8,
+    pub(crate) gl_version: (u8, u8),
+    pub(crate) gl_profile: GlProfile,
+    pub(crate) logical_size: Option<(i32, i32)>,
+    pub(crate) auto_scale: bool,
+    pub(crate) scaling_mode: ScalingMode,
     pub(crate) stencil_buffer: bool,
     pub(crate) high_dpi: bool,
     pub(crate) screen_saver_enabled: bool,
@@ -222,8 +313,11 @@ pub struct ContextBuilder {
     pub(crate) grab_mouse: bool,
     pub(crate) relative_mouse_mode: bool,
     pub(crate) quit_on_escape: bool,
+    pub(crate) quit_on_close: bool,
     pub(crate) fps_limit: bool,
+    pub(crate) event_mode: EventMode,
     pub(crate) debug_info: bool,
+    pub(crate) generate_mipmaps: bool,
 }
 
 impl ContextBuilder {
@@ -241,6 +335,33 @@ impl ContextBuilder {
         }
     }
 
+    /// Create a new `ContextBuilder`, with a title and the values from a [`Settings`] struct.
+    ///
+    /// This is useful if you want to load your game's window settings from a config file.
+    pub fn from_settings<S>(title: S, settings: &Settings) -> ContextBuilder
+    where
+        S: Into<String>,
+    {
+        let mut builder = ContextBuilder {
+            title: title.into(),
+            ..ContextBuilder::default()
+        };
+
+        builder.settings(settings);
+        builder
+    }
+
+    /// Applies the values from a [`Settings`] struct to the builder, overwriting any
+    /// that have previously been set.
+    pub fn settings(&mut self, settings: &Settings) -> &mut ContextBuilder {
+        self.window_width = settings.window_width;
+        self.window_height = settings.window_height;
+        self.fullscreen = settings.fullscreen;
+        self.vsync = settings.vsync;
+        self.scaling_mode = settings.scaling_mode;
+        self
+    }
+
     /// Sets the title of the window.
     ///
     /// Defaults to `"Tetra"`.
@@ -286,6 +407,17 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets how the game loop should wait for events.
+    ///
+    /// Using [`EventMode::WaitWithTimeout`] can reduce CPU usage for applications that
+    /// are idle most of the time, such as tools and editors.
+    ///
+    /// Defaults to [`EventMode::Poll`].
+    pub fn event_mode(&mut self, event_mode: EventMode) -> &mut ContextBuilder {
+        self.event_mode = event_mode;
+        self
+    }
+
     /// Sets the game's timestep.
     ///
     /// Defaults to `Timestep::Fixed(60.0)`.
@@ -294,6 +426,41 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets the logical size of the game's screen, which will be used when
+    /// [`auto_scale`](Self::auto_scale) is enabled.
+    ///
+    /// Defaults to the size of the window.
+    pub fn logical_size(&mut self, width: i32, height: i32) -> &mut ContextBuilder {
+        self.logical_size = Some((width, height));
+        self
+    }
+
+    /// Sets whether or not the game's screen should be automatically scaled to fit the window.
+    ///
+    /// When this is enabled, everything that is drawn to the screen will be rendered to a
+    /// canvas with the [logical size](Self::logical_size), which will then be scaled up to fit
+    /// the window at the end of each frame, using the [scaling mode](Self::scaling_mode). This
+    /// will be updated automatically when the window is resized, and the mouse position will
+    /// be reported in logical co-ordinates.
+    ///
+    /// The [`ScreenScaler`](crate::graphics::scaling::ScreenScaler) that is being used can be
+    /// accessed via [`scaling::get_auto_scaler`](crate::graphics::scaling::get_auto_scaler) -
+    /// for example, to change the scaling mode.
+    ///
+    /// Defaults to `false`.
+    pub fn auto_scale(&mut self, auto_scale: bool) -> &mut ContextBuilder {
+        self.auto_scale = auto_scale;
+        self
+    }
+
+    /// Sets the scaling mode that will be used when [`auto_scale`](Self::auto_scale) is enabled.
+    ///
+    /// Defaults to `ScalingMode::ShowAllPixelPerfect`.
+    pub fn scaling_mode(&mut self, scaling_mode: ScalingMode) -> &mut ContextBuilder {
+        self.scaling_mode = scaling_mode;
+        self
+    }
+
     /// Sets whether or not the window should start in fullscreen.
     ///
     /// Defaults to `false`.
@@ -348,6 +515,23 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets the version and profile of OpenGL that should be requested when creating the
+    /// graphics context.
+    ///
+    /// Tetra requires at least OpenGL 3.2 - requesting a newer version can be useful if your
+    /// custom shaders rely on newer features, and requesting a compatibility profile can be
+    /// useful on drivers that don't fully support the core profile. Note that macOS only
+    /// supports OpenGL 3.2 and above via the core profile.
+    ///
+    /// If the requested context cannot be created, [`build`](Self::build) will return an error.
+    ///
+    /// Defaults to `3, 2, GlProfile::Core`.
+    pub fn gl_version(&mut self, major: u8, minor: u8, profile: GlProfile) -> &mut ContextBuilder {
+        self.gl_version = (major, minor);
+        self.gl_profile = profile;
+        self
+    }
+
     /// Sets whether or not the window should have a stencil buffer.
     ///
     /// If this is enabled, you can use the stencil functions in the
@@ -449,6 +633,35 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets whether or not the game should close when the user tries to close the window
+    /// (e.g. by clicking the close button, or pressing Alt+F4).
+    ///
+    /// Either way, an [`Event::QuitRequested`](crate::Event::QuitRequested) will be sent
+    /// to [`State::event`]. If this is disabled, the game will keep running, and it is up
+    /// to you to call [`window::quit`](crate::window::quit) when you're ready to exit -
+    /// for example, after asking the player whether they want to save their progress.
+    ///
+    /// Defaults to `true`.
+    pub fn quit_on_close(&mut self, quit_on_close: bool) -> &mut ContextBuilder {
+        self.quit_on_close = quit_on_close;
+        self
+    }
+
+    /// Sets whether or not mipmaps should be generated automatically for newly loaded textures.
+    ///
+    /// This applies to every texture created via the `Texture` constructors (including
+    /// [`Texture::from_data`](crate::graphics::Texture::from_data)), and their mipmaps will
+    /// be regenerated whenever their data is changed. It does not apply to canvases, or to
+    /// textures used internally by fonts. See
+    /// [`Texture::generate_mipmaps`](crate::graphics::Texture::generate_mipmaps) for more
+    /// information about mipmaps, and their memory cost.
+    ///
+    /// Defaults to `false`.
+    pub fn generate_mipmaps(&mut self, generate_mipmaps: bool) -> &mut ContextBuilder {
+        self.generate_mipmaps = generate_mipmaps;
+        self
+    }
+
     /// Sets whether or not the game should print out debug info at startup.
     /// Please include this if you're submitting a bug report!
     pub fn debug_info(&mut self, debug_info: bool) -> &mut ContextBuilder {
@@ -480,6 +693,11 @@ impl Default for ContextBuilder {
             resizable: false,
             borderless: false,
             multisampling: 0,
+            gl_version: (3, 2),
+            gl_profile: GlProfile::Core,
+            logical_size: None,
+            auto_scale: false,
+            scaling_mode: ScalingMode::ShowAllPixelPerfect,
             stencil_buffer: false,
             high_dpi: false,
             screen_saver_enabled: false,
@@ -488,8 +706,77 @@ impl Default for ContextBuilder {
             grab_mouse: false,
             relative_mouse_mode: false,
             quit_on_escape: false,
+            quit_on_close: true,
             fps_limit: true,
+            event_mode: EventMode::Poll,
             debug_info: false,
+            generate_mipmaps: false,
+        }
+    }
+}
+
+/// User-configurable window settings, which can be persisted between runs of a game.
+///
+/// These can be applied when starting up via [`ContextBuilder::from_settings`] or
+/// [`ContextBuilder::settings`], and the current values can be retrieved by calling
+/// [`Settings::from_context`]. This makes it easy to implement a settings menu that
+/// saves its values to a config file.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature. Any fields that are missing when
+/// deserializing will be set to their default values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Settings {
+    /// The width of the window. Defaults to `1280`.
+    pub window_width: i32,
+
+    /// The height of the window. Defaults to `720`.
+    pub window_height: i32,
+
+    /// Whether or not the window should be fullscreen. Defaults to `false`.
+    pub fullscreen: bool,
+
+    /// Whether or not vsync should be enabled. Defaults to `true`.
+    pub vsync: bool,
+
+    /// The scaling mode that should be used when [`ContextBuilder::auto_scale`] is enabled.
+    /// Defaults to `ScalingMode::ShowAllPixelPerfect`.
+    pub scaling_mode: ScalingMode,
+}
+
+impl Settings {
+    /// Creates a `Settings` struct containing the current state of the window.
+    pub fn from_context(ctx: &Context) -> Settings {
+        let (window_width, window_height) = ctx.window.get_window_size();
+
+        Settings {
+            window_width,
+            window_height,
+            fullscreen: ctx.window.is_fullscreen(),
+            vsync: ctx.window.is_vsync_enabled(),
+            scaling_mode: ctx
+                .screen_scaler
+                .as_ref()
+                .map_or(ScalingMode::ShowAllPixelPerfect, |s| s.mode()),
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            window_width: 1280,
+            window_height: 720,
+            fullscreen: false,
+            vsync: true,
+            scaling_mode: ScalingMode::ShowAllPixelPerfect,
         }
     }
 }
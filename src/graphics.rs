@@ -11,7 +11,9 @@ mod canvas;
 mod color;
 mod drawparams;
 mod image_data;
+mod interpolated;
 pub mod mesh;
+mod primitives;
 mod rectangle;
 pub mod scaling;
 mod shader;
@@ -23,6 +25,8 @@ pub use canvas::*;
 pub use color::*;
 pub use drawparams::*;
 pub use image_data::*;
+pub use interpolated::*;
+pub use primitives::*;
 pub use rectangle::*;
 pub use shader::*;
 pub use texture::*;
@@ -47,11 +51,18 @@ pub(crate) struct GraphicsContext {
     texture: Option<Texture>,
     default_texture: Texture,
     default_filter_mode: FilterMode,
+    generate_mipmaps: bool,
 
     shader: Option<Shader>,
     default_shader: Shader,
 
     canvas: Option<Canvas>,
+    canvas_stack: Vec<CanvasState>,
+
+    scissor: Option<Rectangle<i32>>,
+    screen_scissor: Option<Rectangle<i32>>,
+
+    letterbox_color: Option<Color>,
 
     projection_matrix: Mat4<f32>,
     transform_matrix: Mat4<f32>,
@@ -60,6 +71,26 @@ pub(crate) struct GraphicsContext {
     element_count: usize,
 
     blend_state: BlendState,
+    polygon_mode: PolygonMode,
+
+    render_stats: RenderStats,
+    last_render_stats: RenderStats,
+}
+
+/// Counters for the rendering work done in a frame, which are exposed to the user via
+/// [`time::get_frame_stats`](crate::time::get_frame_stats).
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RenderStats {
+    pub(crate) draw_calls: usize,
+    pub(crate) batched_quads: usize,
+}
+
+/// The render state that gets saved by [`push_canvas`], so that it can be restored by
+/// [`pop_canvas`].
+struct CanvasState {
+    canvas: Option<Canvas>,
+    shader: Option<Shader>,
+    scissor: Option<Rectangle<i32>>,
 }
 
 impl GraphicsContext {
@@ -67,6 +98,7 @@ impl GraphicsContext {
         device: &mut GraphicsDevice,
         window_width: i32,
         window_height: i32,
+        generate_mipmaps: bool,
     ) -> Result<GraphicsContext> {
         let vertex_buffer = device.new_vertex_buffer(MAX_VERTICES, BufferUsage::Dynamic)?;
         let index_buffer = device.new_index_buffer(MAX_INDICES, BufferUsage::Static)?;
@@ -105,11 +137,18 @@ impl GraphicsContext {
             texture: None,
             default_texture,
             default_filter_mode,
+            generate_mipmaps,
 
             shader: None,
             default_shader,
 
             canvas: None,
+            canvas_stack: Vec::new(),
+
+            scissor: None,
+            screen_scissor: None,
+
+            letterbox_color: None,
 
             projection_matrix: ortho(window_width as f32, window_height as f32, false),
             transform_matrix: Mat4::identity(),
@@ -118,6 +157,10 @@ impl GraphicsContext {
             element_count: 0,
 
             blend_state: BlendState::default(),
+            polygon_mode: PolygonMode::Fill,
+
+            render_stats: RenderStats::default(),
+            last_render_stats: RenderStats::default(),
         })
     }
 }
@@ -127,6 +170,62 @@ pub fn clear(ctx: &mut Context, color: Color) {
     ctx.device.clear(color);
 }
 
+/// Draws a texture repeatedly across the given rectangle, rather than stretching it.
+///
+/// The rectangle is relative to the [`DrawParams`] - for example, a `dest` of
+/// `Rectangle::new(0.0, 0.0, 320.0, 240.0)` with a position of `(16.0, 16.0)` would cover
+/// the area from `(16.0, 16.0)` to `(336.0, 256.0)`. If the size of the rectangle is not a
+/// multiple of the texture's size, the tiles at the right and bottom edges will be cut off.
+///
+/// This is done by drawing a single quad with texture co-ordinates that extend beyond the
+/// edge of the texture, so it is much faster than drawing each tile individually.
+///
+/// If the texture's [`WrapMode`] is set to [`WrapMode::Clamp`], [`WrapMode::Repeat`] will
+/// be used for this quad only, so that the tiling can take effect. This requires the quad
+/// to be drawn in a batch of its own - if you are drawing a texture tiled many times per
+/// frame, set its wrap mode to [`WrapMode::Repeat`] or [`WrapMode::MirroredRepeat`]
+/// beforehand via [`Texture::set_wrap_mode`], to allow the quads to be batched.
+pub fn draw_tiled<P>(ctx: &mut Context, texture: &Texture, dest: Rectangle, params: P)
+where
+    P: Into<DrawParams>,
+{
+    let mut params = params.into();
+
+    params.resolve_anchor_in(dest.x, dest.y, dest.width, dest.height);
+
+    set_texture(ctx, texture);
+
+    // The clamped texture may already be in use elsewhere in this batch, so the quad needs
+    // to be flushed on its own, and the texture's wrap mode restored afterwards.
+    let override_wrap_mode = texture.wrap_mode() == WrapMode::Clamp;
+
+    if override_wrap_mode {
+        flush(ctx);
+        texture.override_wrap_mode(&mut ctx.device, WrapMode::Repeat);
+    }
+
+    let texture_width = texture.width() as f32;
+    let texture_height = texture.height() as f32;
+
+    push_quad(
+        ctx,
+        dest.x,
+        dest.y,
+        dest.right(),
+        dest.bottom(),
+        0.0,
+        0.0,
+        dest.width / texture_width,
+        dest.height / texture_height,
+        &params,
+    );
+
+    if override_wrap_mode {
+        flush(ctx);
+        texture.override_wrap_mode(&mut ctx.device, WrapMode::Clamp);
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn push_quad(
     ctx: &mut Context,
@@ -202,6 +301,20 @@ pub(crate) fn push_quad(
     ctx.graphics.element_count += 6;
 }
 
+pub(crate) fn push_vertices(ctx: &mut Context, vertices: [(Vec2<f32>, Color); 4]) {
+    if ctx.graphics.element_count + 6 > MAX_INDICES {
+        flush(ctx);
+    }
+
+    for (position, color) in vertices {
+        ctx.graphics
+            .vertex_data
+            .push(Vertex::new(position, Vec2::zero(), color));
+    }
+
+    ctx.graphics.element_count += 6;
+}
+
 pub(crate) fn set_texture(ctx: &mut Context, texture: &Texture) {
     set_texture_ex(ctx, Some(texture));
 }
@@ -256,13 +369,68 @@ pub(crate) fn set_shader_ex(ctx: &mut Context, shader: Option<&Shader>) {
 ///
 /// If the canvas is different from the one that is currently in use, this will trigger a
 /// [`flush`] to the graphics hardware.
+///
+/// The viewport will be resized to fit the canvas, and the [scissor rectangle](set_scissor)
+/// will be disabled, as it is specific to the target being drawn to. The screen's scissor
+/// rectangle will be restored when [`reset_canvas`] is called.
+///
+/// If you need to temporarily switch to a different canvas and then return to the
+/// previous one (e.g. when rendering nested targets), use [`push_canvas`] and
+/// [`pop_canvas`] instead.
 pub fn set_canvas(ctx: &mut Context, canvas: &Canvas) {
     set_canvas_ex(ctx, Some(canvas));
 }
 
 /// Sets the renderer back to drawing to the screen directly.
+///
+/// The viewport and scissor rectangle that were being used for the screen will be restored.
+///
+/// If [`ContextBuilder::auto_scale`](crate::ContextBuilder::auto_scale) is enabled, this will
+/// set the renderer back to drawing to the logical screen, rather than the window.
 pub fn reset_canvas(ctx: &mut Context) {
-    set_canvas_ex(ctx, None);
+    let screen = scaling::auto_scale_canvas(ctx);
+    set_canvas_ex(ctx, screen.as_ref());
+}
+
+/// Saves the current render target, shader and scissor rectangle, and then sets the
+/// renderer to redirect all drawing commands to the specified canvas.
+///
+/// The saved state can be restored by calling [`pop_canvas`]. Calls can be nested, which
+/// allows you to render to a canvas while in the middle of rendering to another target.
+///
+/// If the canvas is different from the one that is currently in use, this will trigger a
+/// [`flush`] to the graphics hardware.
+pub fn push_canvas(ctx: &mut Context, canvas: &Canvas) {
+    let state = CanvasState {
+        canvas: ctx.graphics.canvas.clone(),
+        shader: ctx.graphics.shader.clone(),
+        scissor: ctx.graphics.scissor,
+    };
+
+    ctx.graphics.canvas_stack.push(state);
+
+    set_canvas_ex(ctx, Some(canvas));
+}
+
+/// Restores the render target, shader and scissor rectangle that were saved by the
+/// last call to [`push_canvas`].
+///
+/// If there is no saved state, this is equivalent to calling [`reset_canvas`].
+pub fn pop_canvas(ctx: &mut Context) {
+    match ctx.graphics.canvas_stack.pop() {
+        Some(state) => {
+            let canvas = state.canvas.or_else(|| scaling::auto_scale_canvas(ctx));
+
+            set_shader_ex(ctx, state.shader.as_ref());
+            set_canvas_ex(ctx, canvas.as_ref());
+
+            flush(ctx);
+            ctx.graphics.scissor = state.scissor;
+            apply_scissor(ctx);
+        }
+
+        None => reset_canvas(ctx),
+    }
 }
 
 pub(crate) fn set_canvas_ex(ctx: &mut Context, canvas: Option<&Canvas>) {
@@ -270,6 +438,16 @@ pub(crate) fn set_canvas_ex(ctx: &mut Context, canvas: Option<&Canvas>) {
         flush(ctx);
         resolve_canvas(ctx);
 
+        let from_screen = is_screen(ctx, ctx.graphics.canvas.as_ref());
+        let to_screen = is_screen(ctx, canvas);
+
+        switch_scissor(
+            &mut ctx.graphics.scissor,
+            &mut ctx.graphics.screen_scissor,
+            from_screen,
+            to_screen,
+        );
+
         ctx.graphics.canvas = canvas.cloned();
 
         match &ctx.graphics.canvas {
@@ -292,9 +470,107 @@ pub(crate) fn set_canvas_ex(ctx: &mut Context, canvas: Option<&Canvas>) {
                 ctx.device.set_canvas(Some(&r.handle));
             }
         }
+
+        apply_scissor(ctx);
     }
 }
 
+/// Returns whether the given render target is the one that the game treats as the screen.
+///
+/// When auto scaling is enabled, this is the auto scaler's canvas rather than the window.
+fn is_screen(ctx: &Context, canvas: Option<&Canvas>) -> bool {
+    match &ctx.screen_scaler {
+        Some(scaler) => canvas == Some(scaler.canvas()),
+        None => canvas.is_none(),
+    }
+}
+
+/// Updates the active scissor rectangle when switching between render targets.
+///
+/// The scissor rectangle is relative to the current target, so it shouldn't carry over
+/// when switching - but we do keep track of the screen's, so that it can be restored later.
+fn switch_scissor(
+    scissor: &mut Option<Rectangle<i32>>,
+    screen_scissor: &mut Option<Rectangle<i32>>,
+    from_screen: bool,
+    to_screen: bool,
+) {
+    if from_screen {
+        *screen_scissor = *scissor;
+    }
+
+    *scissor = if to_screen {
+        screen_scissor.take()
+    } else {
+        None
+    };
+}
+
+/// Draws a canvas to the current render target as a full-screen quad, using the specified
+/// shader.
+///
+/// This is a shortcut for implementing post-processing effects (e.g. CRT filters, bloom or
+/// vignettes). The canvas will be stretched to fill the current target (either the screen,
+/// or the active canvas), ignoring the current transform matrix. Once the quad has been
+/// drawn, the previously active shader will be restored.
+///
+/// As well as the standard uniforms, the following will be set on the shader:
+///
+/// * `u_resolution` (`vec2`) - the size of the current render target, in physical pixels.
+///   On high-DPI displays, this may differ from [`window::get_size`].
+/// * `u_time` (`float`) - the amount of time that has passed since the game started
+///   running, in seconds. See [`time::get_elapsed_time`](crate::time::get_elapsed_time).
+///
+/// Multiple effects can be chained together by swapping between two canvases - render
+/// the first pass from canvas A into canvas B, then the second pass from canvas B back
+/// into canvas A, and so on, until the final pass is applied to the screen.
+///
+/// This will trigger a [`flush`] to the graphics hardware.
+pub fn apply_post_process(ctx: &mut Context, source: &Canvas, shader: &Shader) {
+    // The quad is drawn in the target's coordinate space, which for the window is in
+    // logical units - the shader, however, gets the real size of the framebuffer.
+    let ((target_width, target_height), (physical_width, physical_height)) =
+        match &ctx.graphics.canvas {
+            Some(c) => (c.size(), c.size()),
+            None => (window::get_size(ctx), window::get_physical_size(ctx)),
+        };
+
+    let previous_shader = ctx.graphics.shader.clone();
+    let previous_transform = ctx.graphics.transform_matrix;
+
+    set_shader_ex(ctx, Some(shader));
+    flush(ctx);
+
+    ctx.graphics.transform_matrix = Mat4::identity();
+
+    let size = Vec2::new(target_width as f32, target_height as f32);
+    let resolution = Vec2::new(physical_width as f32, physical_height as f32);
+    let time = ctx.time.elapsed_time.as_secs_f32();
+
+    shader.set_uniform(ctx, "u_resolution", resolution);
+    shader.set_uniform(ctx, "u_time", time);
+
+    set_texture(ctx, &source.texture);
+
+    push_quad(
+        ctx,
+        0.0,
+        0.0,
+        size.x,
+        size.y,
+        0.0,
+        0.0,
+        1.0,
+        1.0,
+        &DrawParams::new(),
+    );
+
+    flush(ctx);
+
+    ctx.graphics.transform_matrix = previous_transform;
+    set_shader_ex(ctx, previous_shader.as_ref());
+}
+
 fn resolve_canvas(ctx: &mut Context) {
     if let Some(c) = &ctx.graphics.canvas {
         if c.multisample.is_some() {
@@ -354,6 +630,9 @@ pub fn flush(ctx: &mut Context) {
             ctx.graphics.element_count,
         );
 
+        ctx.graphics.render_stats.draw_calls += 1;
+        ctx.graphics.render_stats.batched_quads += ctx.graphics.element_count / 6;
+
         ctx.graphics.vertex_data.clear();
         ctx.graphics.element_count = 0;
     }
@@ -370,6 +649,55 @@ pub fn present(ctx: &mut Context) {
     flush(ctx);
 
     ctx.window.swap_buffers();
+
+    ctx.graphics.last_render_stats = std::mem::take(&mut ctx.graphics.render_stats);
+}
+
+/// Reads the current contents of the screen back from the GPU.
+///
+/// This will contain everything that has been drawn to the screen so far this frame,
+/// so it should usually be called at the end of [`State::draw`](crate::State::draw),
+/// after all of your rendering is complete. The data is read from the window's
+/// backbuffer at its physical size, regardless of which canvas is currently active.
+///
+/// If [`ContextBuilder::auto_scale`](crate::ContextBuilder::auto_scale) is enabled, the
+/// game is drawn to the logical screen rather than the window during `draw`, so the
+/// data will be read from the logical screen instead, at its logical size. The scaling
+/// and letterboxing that is applied afterwards will not be included.
+///
+/// This can be useful for taking screenshots, or for comparing the output of your game
+/// against a reference image in tests. This is a fairly slow operation, so avoid doing
+/// it too often!
+///
+/// The returned [`ImageData`] will be in [`TextureFormat::Rgba8`] format, and will be
+/// fully opaque.
+pub fn get_screen_data(ctx: &mut Context) -> ImageData {
+    flush(ctx);
+
+    let (width, height, mut buffer) = match scaling::auto_scale_canvas(ctx) {
+        Some(canvas) => {
+            let (width, height) = canvas.size();
+            (width, height, canvas.get_data(ctx).into_bytes())
+        }
+
+        None => {
+            let (width, height) = window::get_physical_size(ctx);
+            (width, height, ctx.device.get_screen_data(width, height))
+        }
+    };
+
+    // The alpha channel isn't meaningful once the image has been composited
+    // onto the window.
+    for pixel in buffer.chunks_exact_mut(4) {
+        pixel[3] = 255;
+    }
+
+    ImageData::from_data(width, height, TextureFormat::Rgba8, buffer)
+        .expect("buffer should be exact size for image")
+}
+
+pub(crate) fn get_last_render_stats(ctx: &Context) -> RenderStats {
+    ctx.graphics.last_render_stats
 }
 
 /// Returns the filter mode that will be used by newly created textures and canvases.
@@ -399,9 +727,36 @@ pub struct GraphicsDeviceInfo {
     pub glsl_version: String,
 }
 
+/// The profiles that can be requested when creating an OpenGL context.
+///
+/// See [`ContextBuilder::gl_version`](crate::ContextBuilder::gl_version) for more information.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum GlProfile {
+    /// The core profile, which does not include any deprecated functionality.
+    ///
+    /// This is the default.
+    Core,
+
+    /// The compatibility profile, which includes deprecated functionality for
+    /// backwards compatibility.
+    Compatibility,
+}
+
 /// Retrieves information about the device currently being used to render graphics.
 ///
-/// This may be useful for debugging/logging purposes.
+/// This may be useful for debugging/logging purposes - for example, including it in bug
+/// reports can help to track down driver-specific rendering issues. The version of SDL
+/// being used can be retrieved via [`window::get_sdl_version`].
 pub fn get_device_info(ctx: &Context) -> GraphicsDeviceInfo {
     ctx.device.get_info()
 }
@@ -440,6 +795,27 @@ pub fn reset_transform_matrix(ctx: &mut Context) {
 pub fn set_scissor(ctx: &mut Context, scissor_rect: Rectangle<i32>) {
     flush(ctx);
 
+    ctx.graphics.scissor = Some(scissor_rect);
+    apply_scissor(ctx);
+}
+
+/// Disables the scissor rectangle.
+pub fn reset_scissor(ctx: &mut Context) {
+    flush(ctx);
+
+    ctx.graphics.scissor = None;
+    apply_scissor(ctx);
+}
+
+fn apply_scissor(ctx: &mut Context) {
+    let scissor_rect = match ctx.graphics.scissor {
+        Some(r) => r,
+        None => {
+            ctx.device.scissor_test(false);
+            return;
+        }
+    };
+
     match &ctx.graphics.canvas {
         None => {
             let physical_height = window::get_physical_height(ctx);
@@ -470,13 +846,6 @@ pub fn set_scissor(ctx: &mut Context, scissor_rect: Rectangle<i32>) {
     ctx.device.scissor_test(true);
 }
 
-/// Disables the scissor rectangle.
-pub fn reset_scissor(ctx: &mut Context) {
-    flush(ctx);
-
-    ctx.device.scissor_test(false);
-}
-
 /// Sets the global stencil behavior.
 ///
 /// The stencil buffer is an invisible drawing target that you can
@@ -512,6 +881,103 @@ pub fn set_color_mask(ctx: &mut Context, red: bool, green: bool, blue: bool, alp
     ctx.device.set_color_mask(red, green, blue, alpha);
 }
 
+/// Sets how polygons should be rasterized.
+///
+/// Setting this to [`PolygonMode::Line`] will draw the edges of each triangle, rather than
+/// filling them in. This can be useful for debugging the geometry of meshes, or for checking
+/// how your drawing operations are being batched.
+///
+/// This will trigger a [`flush`] to the graphics hardware.
+///
+/// # Platform-specific notes
+///
+/// This requires desktop OpenGL - it is not available in OpenGL ES. If the current
+/// context does not support it, a warning will be printed to stderr and the polygon
+/// mode will not be changed.
+pub fn set_polygon_mode(ctx: &mut Context, mode: PolygonMode) {
+    flush(ctx);
+
+    if ctx.device.set_polygon_mode(mode) {
+        ctx.graphics.polygon_mode = mode;
+    } else {
+        eprintln!("Warning: polygon modes are not supported by the current OpenGL context");
+    }
+}
+
+/// Ways of rasterizing polygons.
+///
+/// See [`set_polygon_mode`] for more information.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonMode {
+    /// Polygons will be filled in. This is the default.
+    Fill,
+
+    /// Only the edges of polygons will be drawn. This is mainly useful for debugging.
+    Line,
+}
+
+/// Returns the region of the window that the game is being displayed in, in physical pixels.
+///
+/// Usually, this will be the whole of the window's drawable area, which may be larger than
+/// the logical size of the window on high-DPI displays.
+///
+/// If [`ContextBuilder::auto_scale`](crate::ContextBuilder::auto_scale) is enabled, this
+/// will instead be the region that the scaled image is drawn to, not including any
+/// letterbox bars. This can be useful for mapping raw window coordinates, or for drawing
+/// into the bars.
+///
+/// If you are using your own [`ScreenScaler`](scaling::ScreenScaler), the region that it
+/// draws to can be retrieved (in logical pixels) via
+/// [`ScreenScaler::screen_rect`](scaling::ScreenScaler::screen_rect).
+pub fn get_viewport(ctx: &Context) -> Rectangle<i32> {
+    let (physical_width, physical_height) = window::get_physical_size(ctx);
+
+    match &ctx.screen_scaler {
+        Some(scaler) => {
+            let (outer_width, outer_height) = scaler.outer_size();
+
+            if outer_width <= 0 || outer_height <= 0 {
+                return Rectangle::new(0, 0, 0, 0);
+            }
+
+            // The scaler works in logical pixels, so its output needs to be converted.
+            let scale_x = physical_width as f32 / outer_width as f32;
+            let scale_y = physical_height as f32 / outer_height as f32;
+            let screen_rect = scaler.screen_rect();
+
+            Rectangle::new(
+                (screen_rect.x * scale_x).round() as i32,
+                (screen_rect.y * scale_y).round() as i32,
+                (screen_rect.width * scale_x).round() as i32,
+                (screen_rect.height * scale_y).round() as i32,
+            )
+        }
+
+        None => Rectangle::new(0, 0, physical_width, physical_height),
+    }
+}
+
+/// Sets the color that will be used to fill the letterbox bars when drawing a
+/// [`ScreenScaler`](scaling::ScreenScaler).
+///
+/// By default, the bars are left untouched, so whatever was drawn to the screen before the
+/// scaler will show through. This is usually the color passed to [`clear`].
+pub fn set_letterbox_color(ctx: &mut Context, color: Color) {
+    ctx.graphics.letterbox_color = Some(color);
+}
+
+/// Stops the letterbox bars from being filled when drawing a
+/// [`ScreenScaler`](scaling::ScreenScaler).
+pub fn reset_letterbox_color(ctx: &mut Context) {
+    ctx.graphics.letterbox_color = None;
+}
+
+/// Returns the color that will be used to fill the letterbox bars, if one has been set.
+pub fn get_letterbox_color(ctx: &Context) -> Option<Color> {
+    ctx.graphics.letterbox_color
+}
+
 pub(crate) fn set_viewport_size(ctx: &mut Context) {
     if ctx.graphics.canvas.is_none() {
         let (width, height) = window::get_size(ctx);
@@ -519,6 +985,10 @@ pub(crate) fn set_viewport_size(ctx: &mut Context) {
 
         ctx.graphics.projection_matrix = ortho(width as f32, height as f32, false);
         ctx.device.viewport(0, 0, physical_width, physical_height);
+
+        // The scissor rect's Y co-ordinate is flipped based on the size of the
+        // window, so it needs recalculating.
+        apply_scissor(ctx);
     }
 }
 
@@ -995,3 +1465,46 @@ impl StencilState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_scissor_is_restored_after_canvas() {
+        let rect = Rectangle::new(8, 8, 32, 32);
+
+        let mut scissor = Some(rect);
+        let mut screen_scissor = None;
+
+        // Screen -> canvas
+        switch_scissor(&mut scissor, &mut screen_scissor, true, false);
+        assert_eq!(None, scissor);
+
+        // Canvas -> another canvas
+        scissor = Some(Rectangle::new(0, 0, 4, 4));
+        switch_scissor(&mut scissor, &mut screen_scissor, false, false);
+        assert_eq!(None, scissor);
+
+        // Canvas -> screen
+        switch_scissor(&mut scissor, &mut screen_scissor, false, true);
+        assert_eq!(Some(rect), scissor);
+    }
+
+    #[test]
+    fn auto_scale_screen_scissor_survives_presenting() {
+        let rect = Rectangle::new(8, 8, 32, 32);
+
+        // The logical screen (the auto scale canvas) has a scissor set during `draw`.
+        let mut scissor = Some(rect);
+        let mut screen_scissor = None;
+
+        // Logical screen -> window, to draw the scaler.
+        switch_scissor(&mut scissor, &mut screen_scissor, true, false);
+        assert_eq!(None, scissor);
+
+        // Window -> logical screen, at the start of the next frame.
+        switch_scissor(&mut scissor, &mut screen_scissor, false, true);
+        assert_eq!(Some(rect), scissor);
+    }
+}
@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use crate::error::Result;
-use crate::graphics::{DrawParams, FilterMode, Texture};
+use crate::graphics::{DrawParams, FilterMode, Texture, WrapMode};
 use crate::platform::{RawCanvas, RawRenderbuffer};
 use crate::Context;
 
@@ -183,6 +183,16 @@ impl Canvas {
         self.texture.set_filter_mode(ctx, filter_mode);
     }
 
+    /// Returns the wrap mode being used by the canvas.
+    pub fn wrap_mode(&self) -> WrapMode {
+        self.texture.wrap_mode()
+    }
+
+    /// Sets the wrap mode that should be used by the canvas.
+    pub fn set_wrap_mode(&mut self, ctx: &mut Context, wrap_mode: WrapMode) {
+        self.texture.set_wrap_mode(ctx, wrap_mode);
+    }
+
     /// Gets the canvas' data from the GPU.
     ///
     /// This can be useful if you need to do some image processing on the CPU,
@@ -20,8 +20,23 @@ pub struct DrawParams {
     /// This offset is applied before scaling, rotation and positioning. For example, if you have
     /// a 16x16 image and set the origin to [8.0, 8.0], subsequent transformations will be performed
     /// relative to the center of the image.
+    ///
+    /// If an [`anchor`](DrawParams::anchor) is set, this is instead treated as an offset
+    /// from the anchor point.
     pub origin: Vec2<f32>,
 
+    /// An anchor point to use as the origin of the graphic. Defaults to `None`.
+    ///
+    /// If this is set, the anchor point will be calculated from the size of the graphic
+    /// when it is drawn, and the value of `origin` will be added to it. This means that
+    /// the two can be combined regardless of the order they are set in - for example,
+    /// an anchor of [`Anchor::BottomCenter`] with an origin of `(0.0, -4.0)` will place
+    /// the origin four pixels above the bottom edge.
+    ///
+    /// Graphics that do not have a known size (such as a [`Mesh`](crate::graphics::mesh::Mesh))
+    /// will ignore the anchor.
+    pub anchor: Option<Anchor>,
+
     /// The rotation of the graphic, in radians. Defaults to `0.0`.
     pub rotation: f32,
 
@@ -48,11 +63,24 @@ impl DrawParams {
     }
 
     /// Sets the origin of the graphic.
+    ///
+    /// If an [`Anchor`] is also set, the origin will be treated as an offset from the
+    /// anchor point.
     pub fn origin(mut self, origin: Vec2<f32>) -> DrawParams {
         self.origin = origin;
         self
     }
 
+    /// Sets the origin of the graphic to an anchor point, which will be
+    /// calculated from the graphic's size when it is drawn.
+    ///
+    /// Any [`origin`](DrawParams::origin) that is set will be added to the anchor point,
+    /// regardless of whether it was set before or after the anchor.
+    pub fn anchor(mut self, anchor: Anchor) -> DrawParams {
+        self.anchor = Some(anchor);
+        self
+    }
+
     /// Sets the rotation of the graphic, in radians.
     pub fn rotation(mut self, rotation: f32) -> DrawParams {
         self.rotation = rotation;
@@ -76,6 +104,20 @@ impl DrawParams {
         matrix.translate_2d(self.position);
         matrix
     }
+
+    /// Resolves the anchor (if one is set) into a concrete origin, based on the
+    /// size of the graphic being drawn.
+    pub(crate) fn resolve_anchor(&mut self, width: f32, height: f32) {
+        self.resolve_anchor_in(0.0, 0.0, width, height);
+    }
+
+    /// Resolves the anchor (if one is set) into a concrete origin, based on the
+    /// bounds of the graphic being drawn.
+    pub(crate) fn resolve_anchor_in(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        if let Some(anchor) = self.anchor.take() {
+            self.origin += Vec2::new(x, y) + anchor.to_origin(width, height);
+        }
+    }
 }
 
 impl Default for DrawParams {
@@ -84,6 +126,7 @@ impl Default for DrawParams {
             position: Vec2::new(0.0, 0.0),
             scale: Vec2::new(1.0, 1.0),
             origin: Vec2::new(0.0, 0.0),
+            anchor: None,
             rotation: 0.0,
             color: Color::WHITE,
         }
@@ -104,3 +147,92 @@ impl From<DrawParams> for Mat4<f32> {
         params.to_matrix()
     }
 }
+
+/// Preset anchor points that can be used as the origin of a graphic.
+///
+/// These can be passed to [`DrawParams::anchor`] to avoid having to calculate
+/// the origin by hand.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Anchor {
+    /// The top left corner of the graphic.
+    TopLeft,
+
+    /// The center of the top edge of the graphic.
+    TopCenter,
+
+    /// The top right corner of the graphic.
+    TopRight,
+
+    /// The center of the left edge of the graphic.
+    CenterLeft,
+
+    /// The center of the graphic.
+    Center,
+
+    /// The center of the right edge of the graphic.
+    CenterRight,
+
+    /// The bottom left corner of the graphic.
+    BottomLeft,
+
+    /// The center of the bottom edge of the graphic.
+    BottomCenter,
+
+    /// The bottom right corner of the graphic.
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the origin that this anchor point represents, for a graphic
+    /// of the given size.
+    pub fn to_origin(self, width: f32, height: f32) -> Vec2<f32> {
+        let (x, y) = match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::TopCenter => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::CenterLeft => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::CenterRight => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::BottomCenter => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0),
+        };
+
+        Vec2::new(width * x, height * y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchor_resolves_to_origin() {
+        let mut params = DrawParams::new().anchor(Anchor::BottomCenter);
+        params.resolve_anchor(16.0, 32.0);
+
+        assert_eq!(params.origin, Vec2::new(8.0, 32.0));
+        assert_eq!(params.anchor, None);
+    }
+
+    #[test]
+    fn origin_offsets_anchor_regardless_of_order() {
+        let mut anchor_first = DrawParams::new()
+            .anchor(Anchor::Center)
+            .origin(Vec2::new(2.0, 3.0));
+        anchor_first.resolve_anchor(16.0, 32.0);
+
+        let mut origin_first = DrawParams::new()
+            .origin(Vec2::new(2.0, 3.0))
+            .anchor(Anchor::Center);
+        origin_first.resolve_anchor(16.0, 32.0);
+
+        assert_eq!(anchor_first.origin, Vec2::new(10.0, 19.0));
+        assert_eq!(origin_first.origin, Vec2::new(10.0, 19.0));
+    }
+}
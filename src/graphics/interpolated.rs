@@ -0,0 +1,130 @@
+use crate::math::Lerp;
+use crate::time;
+use crate::Context;
+
+/// A value that can be smoothly interpolated between updates.
+///
+/// When using a [fixed timestep](crate::time::Timestep::Fixed), the game may render
+/// multiple times between updates (or skip rendering entirely for some updates). To
+/// avoid stutter, rendering code should blend between the state of the game before
+/// and after the most recent update, using the [blend factor](crate::time::get_blend_factor).
+///
+/// `Interpolated` stores both of those states for you. Call [`set`](Interpolated::set)
+/// once per update with the new value, and then call [`get`](Interpolated::get) while
+/// drawing to retrieve the blended value.
+///
+/// If the value jumps to a new position (e.g. an entity being teleported, or wrapping
+/// around the edge of the screen), use [`snap`](Interpolated::snap) instead of `set`,
+/// so that it does not appear to slide across the screen.
+///
+/// Any type that implements [`Lerp`] can be used, which includes [`f32`] and
+/// [`Vec2<f32>`](crate::math::Vec2).
+///
+/// # Examples
+///
+/// ```
+/// # use tetra::graphics::Interpolated;
+/// # use tetra::math::Vec2;
+/// let mut position = Interpolated::new(Vec2::new(0.0, 0.0));
+///
+/// // In `update`:
+/// position.set(Vec2::new(10.0, 0.0));
+///
+/// // In `draw` (usually via `position.get(time::get_blend_factor(ctx))`):
+/// assert_eq!(Vec2::new(5.0, 0.0), position.get(0.5));
+///
+/// // Jumping to a new position:
+/// position.snap(Vec2::new(100.0, 0.0));
+///
+/// assert_eq!(Vec2::new(100.0, 0.0), position.get(0.5));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Interpolated<T> {
+    previous: T,
+    current: T,
+}
+
+impl<T> Interpolated<T>
+where
+    T: Lerp<f32, Output = T> + Copy,
+{
+    /// Creates a new `Interpolated` value, with both the previous and current state set
+    /// to the specified value.
+    pub fn new(value: T) -> Interpolated<T> {
+        Interpolated {
+            previous: value,
+            current: value,
+        }
+    }
+
+    /// Sets the current value, moving the old current value into the previous state.
+    ///
+    /// This should be called once per update.
+    pub fn set(&mut self, value: T) {
+        self.previous = self.current;
+        self.current = value;
+    }
+
+    /// Sets both the previous and current state to the specified value, so that no
+    /// interpolation will occur until the next call to [`set`](Interpolated::set).
+    pub fn snap(&mut self, value: T) {
+        self.previous = value;
+        self.current = value;
+    }
+
+    /// Returns the value, blended between the previous and current state.
+    ///
+    /// `blend` should be between `0.0` (the previous state) and `1.0` (the current
+    /// state) - usually, you will want to pass in the value of
+    /// [`time::get_blend_factor`](crate::time::get_blend_factor).
+    pub fn get(&self, blend: f32) -> T {
+        T::lerp(self.previous, self.current, blend)
+    }
+
+    /// Returns the value, blended between the previous and current state using the
+    /// game's current [blend factor](crate::time::get_blend_factor).
+    ///
+    /// This is a shortcut for calling
+    /// [`get(time::get_blend_factor(ctx))`](Interpolated::get).
+    pub fn get_blended(&self, ctx: &Context) -> T {
+        self.get(time::get_blend_factor(ctx))
+    }
+
+    /// Returns the previous state.
+    pub fn previous(&self) -> T {
+        self.previous
+    }
+
+    /// Returns the current state.
+    pub fn current(&self) -> T {
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_moves_current_to_previous() {
+        let mut value = Interpolated::new(1.0);
+
+        value.set(2.0);
+        value.set(4.0);
+
+        assert_eq!(2.0, value.previous());
+        assert_eq!(4.0, value.current());
+        assert_eq!(3.0, value.get(0.5));
+    }
+
+    #[test]
+    fn snap_skips_interpolation() {
+        let mut value = Interpolated::new(0.0);
+
+        value.set(10.0);
+        value.snap(-50.0);
+
+        assert_eq!(-50.0, value.get(0.0));
+        assert_eq!(-50.0, value.get(1.0));
+    }
+}
@@ -256,13 +256,10 @@ struct DrawRange {
 }
 
 /// Ways of drawing a shape.
-#[derive(Copy, Clone, Debug)]
-pub enum ShapeStyle {
-    /// A filled shape.
-    Fill,
-    /// An outlined shape with the specified stroke width.
-    Stroke(f32),
-}
+///
+/// This is an alias for [`DrawMode`](crate::graphics::DrawMode), so that meshes and the
+/// shape drawing functions in the [`graphics`](crate::graphics) module behave consistently.
+pub type ShapeStyle = graphics::DrawMode;
 
 /// A 2D mesh that can be drawn to the screen.
 ///
@@ -403,6 +400,8 @@ impl Mesh {
             count,
             instances,
         );
+
+        ctx.graphics.render_stats.draw_calls += 1;
     }
 
     /// Gets a reference to the vertex buffer contained within this mesh.
@@ -0,0 +1,529 @@
+//! Functions and types relating to drawing primitive shapes.
+
+use std::f32::consts::TAU;
+
+use crate::graphics::{self, Color, Rectangle};
+use crate::math::Vec2;
+use crate::Context;
+
+const MITER_LIMIT: f32 = 4.0;
+
+/// Ways of drawing a primitive shape.
+///
+/// This is used by all of the shape drawing functions in the [`graphics`](crate::graphics)
+/// module, as well as by the shape constructors on [`Mesh`](crate::graphics::mesh::Mesh)
+/// and [`GeometryBuilder`](crate::graphics::mesh::GeometryBuilder).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DrawMode {
+    /// A filled shape.
+    Fill,
+
+    /// An outlined shape with the specified stroke width.
+    ///
+    /// The width is measured in pixels, and the stroke is centered on the edge
+    /// of the shape (i.e. half of it will be drawn outside of the shape, and half
+    /// of it will be drawn inside).
+    Stroke(f32),
+}
+
+/// Draws a rectangle to the screen (or to a canvas, if one is enabled).
+///
+/// Shapes drawn via this function are added to the same batch as sprites and text,
+/// so drawing them will not cause a flush unless the current texture needs to change.
+pub fn draw_rectangle(ctx: &mut Context, mode: DrawMode, rectangle: Rectangle, color: Color) {
+    let quads = rectangle_quads(mode, rectangle);
+    push_shape(ctx, &quads, color);
+}
+
+/// Draws a rectangle filled with a vertical gradient to the screen (or to a canvas, if one
+/// is enabled).
+///
+/// The colors will be interpolated from the top edge of the rectangle to the bottom edge.
+/// For horizontal or diagonal gradients, use [`draw_rectangle_gradient_corners`].
+pub fn draw_rectangle_gradient(
+    ctx: &mut Context,
+    rectangle: Rectangle,
+    top_color: Color,
+    bottom_color: Color,
+) {
+    draw_rectangle_gradient_corners(
+        ctx,
+        rectangle,
+        top_color,
+        top_color,
+        bottom_color,
+        bottom_color,
+    );
+}
+
+/// Draws a rectangle filled with a gradient to the screen (or to a canvas, if one is enabled),
+/// with a separate color for each corner.
+///
+/// The colors will be interpolated across the rectangle. For example, setting both left
+/// corners to one color and both right corners to another will produce a horizontal
+/// gradient.
+pub fn draw_rectangle_gradient_corners(
+    ctx: &mut Context,
+    rectangle: Rectangle,
+    top_left: Color,
+    top_right: Color,
+    bottom_right: Color,
+    bottom_left: Color,
+) {
+    let quads = rectangle_quads(DrawMode::Fill, rectangle);
+
+    // The quad's points are exactly the rectangle's corners (possibly re-wound, if the
+    // rectangle has a negative size), so each one can be matched back to its color.
+    push_shape_ex(ctx, &quads, |point| {
+        match (point.y == rectangle.top(), point.x == rectangle.left()) {
+            (true, true) => top_left,
+            (true, false) => top_right,
+            (false, false) => bottom_right,
+            (false, true) => bottom_left,
+        }
+    });
+}
+
+/// Draws a rectangle with rounded corners to the screen (or to a canvas, if one is enabled).
+///
+/// The radius will be clamped to half of the rectangle's smallest dimension, so passing
+/// a very large radius will produce a pill shape (or a circle, if the rectangle is square).
+///
+/// The number of segments used for each corner is calculated automatically based on the
+/// radius - use [`draw_rounded_rectangle_with_segments`] if you need to control this.
+pub fn draw_rounded_rectangle(
+    ctx: &mut Context,
+    mode: DrawMode,
+    rectangle: Rectangle,
+    radius: f32,
+    color: Color,
+) {
+    let segments = corner_segments(radius);
+    draw_rounded_rectangle_with_segments(ctx, mode, rectangle, radius, segments, color);
+}
+
+/// Draws a rectangle with rounded corners to the screen (or to a canvas, if one is enabled),
+/// using the specified number of segments for each corner.
+///
+/// Using more segments will make the corners smoother, at the cost of generating more
+/// geometry.
+///
+/// The radius will be clamped to half of the rectangle's smallest dimension, so passing
+/// a very large radius will produce a pill shape (or a circle, if the rectangle is square).
+pub fn draw_rounded_rectangle_with_segments(
+    ctx: &mut Context,
+    mode: DrawMode,
+    rectangle: Rectangle,
+    radius: f32,
+    segments: usize,
+    color: Color,
+) {
+    let points = rounded_rectangle_points(rectangle, radius, segments);
+    let quads = convex_polygon_quads(mode, &points);
+    push_shape(ctx, &quads, color);
+}
+
+/// Draws a circle to the screen (or to a canvas, if one is enabled).
+///
+/// Shapes drawn via this function are added to the same batch as sprites and text,
+/// so drawing them will not cause a flush unless the current texture needs to change.
+pub fn draw_circle(
+    ctx: &mut Context,
+    mode: DrawMode,
+    center: Vec2<f32>,
+    radius: f32,
+    color: Color,
+) {
+    let quads = circle_quads(mode, center, radius);
+    push_shape(ctx, &quads, color);
+}
+
+/// Draws a convex polygon to the screen (or to a canvas, if one is enabled).
+///
+/// The points will be joined together in order, with the last point being joined back
+/// to the first. Filled polygons are triangulated as a fan from the first point, and
+/// stroked polygons are drawn as a closed loop.
+///
+/// This function assumes that the polygon is convex, as that allows it to be drawn
+/// without any expensive tessellation - concave polygons may render incorrectly. If you
+/// need to draw a concave polygon, use [`Mesh::polygon`](crate::graphics::mesh::Mesh::polygon)
+/// instead.
+///
+/// If fewer than three points are provided, nothing will be drawn.
+pub fn draw_polygon(ctx: &mut Context, mode: DrawMode, points: &[Vec2<f32>], color: Color) {
+    let quads = convex_polygon_quads(mode, points);
+    push_shape(ctx, &quads, color);
+}
+
+/// Draws a line to the screen (or to a canvas, if one is enabled), with the
+/// specified thickness.
+///
+/// Shapes drawn via this function are added to the same batch as sprites and text,
+/// so drawing them will not cause a flush unless the current texture needs to change.
+pub fn draw_line(ctx: &mut Context, from: Vec2<f32>, to: Vec2<f32>, thickness: f32, color: Color) {
+    if let Some(quad) = line_quad(from, to, thickness) {
+        push_shape(ctx, &[quad], color);
+    }
+}
+
+/// A quad of positions in screen co-ordinates. Triangles can be represented by
+/// repeating the last point.
+pub(crate) type Quad = [Vec2<f32>; 4];
+
+/// Pushes a set of untextured quads into the sprite batch.
+pub(crate) fn push_shape(ctx: &mut Context, quads: &[Quad], color: Color) {
+    push_shape_ex(ctx, quads, |_| color);
+}
+
+/// Pushes a set of untextured quads into the sprite batch, calculating the color of
+/// each vertex via the provided function.
+pub(crate) fn push_shape_ex<F>(ctx: &mut Context, quads: &[Quad], color: F)
+where
+    F: Fn(Vec2<f32>) -> Color,
+{
+    let texture = ctx.graphics.default_texture.clone();
+    graphics::set_texture(ctx, &texture);
+
+    for quad in quads {
+        let [a, b, c, d] = *quad;
+
+        // The batch is drawn with backface culling enabled, so the quads need to be
+        // wound consistently.
+        let quad = if cross(b - a, c - a) > 0.0 {
+            [a, d, c, b]
+        } else {
+            [a, b, c, d]
+        };
+
+        graphics::push_vertices(
+            ctx,
+            [
+                (quad[0], color(quad[0])),
+                (quad[1], color(quad[1])),
+                (quad[2], color(quad[2])),
+                (quad[3], color(quad[3])),
+            ],
+        );
+    }
+}
+
+pub(crate) fn rectangle_quads(mode: DrawMode, rectangle: Rectangle) -> Vec<Quad> {
+    match mode {
+        DrawMode::Fill => vec![rect_quad(
+            rectangle.left(),
+            rectangle.top(),
+            rectangle.right(),
+            rectangle.bottom(),
+        )],
+
+        DrawMode::Stroke(width) => {
+            let half = width / 2.0;
+
+            let outer_left = rectangle.left() - half;
+            let outer_top = rectangle.top() - half;
+            let outer_right = rectangle.right() + half;
+            let outer_bottom = rectangle.bottom() + half;
+
+            let inner_left = rectangle.left() + half;
+            let inner_top = rectangle.top() + half;
+            let inner_right = rectangle.right() - half;
+            let inner_bottom = rectangle.bottom() - half;
+
+            if inner_left >= inner_right || inner_top >= inner_bottom {
+                // The stroke is wide enough to cover the whole shape.
+                return vec![rect_quad(outer_left, outer_top, outer_right, outer_bottom)];
+            }
+
+            vec![
+                rect_quad(outer_left, outer_top, outer_right, inner_top),
+                rect_quad(outer_left, inner_bottom, outer_right, outer_bottom),
+                rect_quad(outer_left, inner_top, inner_left, inner_bottom),
+                rect_quad(inner_right, inner_top, outer_right, inner_bottom),
+            ]
+        }
+    }
+}
+
+pub(crate) fn circle_quads(mode: DrawMode, center: Vec2<f32>, radius: f32) -> Vec<Quad> {
+    let segments = circle_segments(radius);
+
+    let point = |i: usize, radius: f32| {
+        let angle = (i as f32 / segments as f32) * TAU;
+        center + Vec2::new(angle.cos(), angle.sin()) * radius
+    };
+
+    match mode {
+        DrawMode::Fill => (0..segments)
+            .map(|i| {
+                let b = point(i, radius);
+                let c = point(i + 1, radius);
+                [center, b, c, c]
+            })
+            .collect(),
+
+        DrawMode::Stroke(width) => {
+            let half = width / 2.0;
+            let outer = radius + half;
+            let inner = (radius - half).max(0.0);
+
+            (0..segments)
+                .map(|i| {
+                    [
+                        point(i, outer),
+                        point(i, inner),
+                        point(i + 1, inner),
+                        point(i + 1, outer),
+                    ]
+                })
+                .collect()
+        }
+    }
+}
+
+pub(crate) fn rounded_rectangle_points(
+    rectangle: Rectangle,
+    radius: f32,
+    segments: usize,
+) -> Vec<Vec2<f32>> {
+    let radius = radius
+        .min(rectangle.width.abs() / 2.0)
+        .min(rectangle.height.abs() / 2.0)
+        .max(0.0);
+
+    let segments = segments.max(1);
+
+    let corners = [
+        (
+            Vec2::new(rectangle.left() + radius, rectangle.top() + radius),
+            0.5,
+        ),
+        (
+            Vec2::new(rectangle.right() - radius, rectangle.top() + radius),
+            0.75,
+        ),
+        (
+            Vec2::new(rectangle.right() - radius, rectangle.bottom() - radius),
+            0.0,
+        ),
+        (
+            Vec2::new(rectangle.left() + radius, rectangle.bottom() - radius),
+            0.25,
+        ),
+    ];
+
+    let mut points = Vec::with_capacity((segments + 1) * 4);
+
+    for (center, start) in corners {
+        for i in 0..=segments {
+            let angle = (start + (i as f32 / segments as f32) * 0.25) * TAU;
+            points.push(center + Vec2::new(angle.cos(), angle.sin()) * radius);
+        }
+    }
+
+    points
+}
+
+/// Generates the geometry for a convex polygon.
+///
+/// Filled polygons are triangulated as a fan, and stroked polygons are drawn as a closed
+/// loop with mitered joins.
+pub(crate) fn convex_polygon_quads(mode: DrawMode, points: &[Vec2<f32>]) -> Vec<Quad> {
+    let mut outline: Vec<Vec2<f32>> = Vec::with_capacity(points.len());
+
+    for &point in points {
+        let is_duplicate = matches!(outline.last(), Some(&last) if (point - last).is_approx_zero());
+
+        if !is_duplicate {
+            outline.push(point);
+        }
+    }
+
+    while outline.len() > 1 && (outline[0] - outline[outline.len() - 1]).is_approx_zero() {
+        outline.pop();
+    }
+
+    if outline.len() < 3 {
+        return Vec::new();
+    }
+
+    let len = outline.len();
+
+    match mode {
+        DrawMode::Fill => (1..len - 1)
+            .map(|i| [outline[0], outline[i], outline[i + 1], outline[i + 1]])
+            .collect(),
+
+        DrawMode::Stroke(width) => {
+            let half = width / 2.0;
+
+            let offsets: Vec<Vec2<f32>> = (0..len)
+                .map(|i| {
+                    let prev = outline[(i + len - 1) % len];
+                    let curr = outline[i];
+                    let next = outline[(i + 1) % len];
+
+                    let n0 = perpendicular((curr - prev).normalized());
+                    let n1 = perpendicular((next - curr).normalized());
+
+                    let miter = n0 + n1;
+
+                    if miter.is_approx_zero() {
+                        return n1 * half;
+                    }
+
+                    let miter = miter.normalized();
+
+                    // Very sharp corners would produce extremely long miters, so they get
+                    // limited to a sensible length.
+                    let length = (half / miter.dot(n1)).min(half * MITER_LIMIT);
+
+                    miter * length
+                })
+                .collect();
+
+            (0..len)
+                .map(|i| {
+                    let j = (i + 1) % len;
+
+                    [
+                        outline[i] + offsets[i],
+                        outline[i] - offsets[i],
+                        outline[j] - offsets[j],
+                        outline[j] + offsets[j],
+                    ]
+                })
+                .collect()
+        }
+    }
+}
+
+pub(crate) fn line_quad(from: Vec2<f32>, to: Vec2<f32>, thickness: f32) -> Option<Quad> {
+    let direction = to - from;
+
+    if direction.is_approx_zero() {
+        return None;
+    }
+
+    let normal = perpendicular(direction.normalized()) * (thickness / 2.0);
+
+    Some([from + normal, from - normal, to - normal, to + normal])
+}
+
+fn rect_quad(left: f32, top: f32, right: f32, bottom: f32) -> Quad {
+    [
+        Vec2::new(left, top),
+        Vec2::new(left, bottom),
+        Vec2::new(right, bottom),
+        Vec2::new(right, top),
+    ]
+}
+
+fn perpendicular(v: Vec2<f32>) -> Vec2<f32> {
+    Vec2::new(-v.y, v.x)
+}
+
+fn cross(a: Vec2<f32>, b: Vec2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn circle_segments(radius: f32) -> usize {
+    // Roughly one segment per 4 pixels of circumference, which is enough to look smooth
+    // at most sizes without generating excessive amounts of geometry.
+    ((radius * TAU) / 4.0).ceil().clamp(12.0, 256.0) as usize
+}
+
+fn corner_segments(radius: f32) -> usize {
+    (circle_segments(radius) / 4).max(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn covers(quads: &[Quad], point: Vec2<f32>) -> bool {
+        quads.iter().any(|&[a, b, c, d]| {
+            let triangle = |a: Vec2<f32>, b: Vec2<f32>, c: Vec2<f32>| {
+                let d1 = cross(b - a, point - a);
+                let d2 = cross(c - b, point - b);
+                let d3 = cross(a - c, point - c);
+
+                (d1 >= 0.0 && d2 >= 0.0 && d3 >= 0.0) || (d1 <= 0.0 && d2 <= 0.0 && d3 <= 0.0)
+            };
+
+            triangle(a, b, c) || triangle(c, d, a)
+        })
+    }
+
+    #[test]
+    fn stroke_and_fill_differ_only_in_interior() {
+        let rect = Rectangle::new(0.0, 0.0, 8.0, 8.0);
+
+        let fill = rectangle_quads(DrawMode::Fill, rect);
+        let stroke = rectangle_quads(DrawMode::Stroke(2.0), rect);
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let pixel = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let is_edge = x == 0 || y == 0 || x == 7 || y == 7;
+
+                assert!(covers(&fill, pixel), "fill should cover {}", pixel);
+                assert_eq!(covers(&stroke, pixel), is_edge, "stroke at {}", pixel);
+            }
+        }
+    }
+
+    #[test]
+    fn stroke_is_centered_on_edge() {
+        let rect = Rectangle::new(0.0, 0.0, 8.0, 8.0);
+        let stroke = rectangle_quads(DrawMode::Stroke(2.0), rect);
+
+        assert!(covers(&stroke, Vec2::new(-0.5, 4.0)));
+        assert!(!covers(&stroke, Vec2::new(-1.5, 4.0)));
+        assert!(covers(&stroke, Vec2::new(8.5, 4.0)));
+        assert!(!covers(&stroke, Vec2::new(9.5, 4.0)));
+    }
+
+    #[test]
+    fn rounded_rectangle_radius_is_clamped() {
+        let rect = Rectangle::new(0.0, 0.0, 16.0, 8.0);
+        let points = rounded_rectangle_points(rect, 100.0, 8);
+
+        for point in points {
+            assert!(point.x >= -0.001 && point.x <= 16.001, "{}", point);
+            assert!(point.y >= -0.001 && point.y <= 8.001, "{}", point);
+        }
+
+        let quads = convex_polygon_quads(DrawMode::Fill, &rounded_rectangle_points(rect, 100.0, 8));
+
+        assert!(covers(&quads, Vec2::new(8.0, 4.0)));
+        assert!(!covers(&quads, Vec2::new(0.5, 0.5)));
+    }
+
+    #[test]
+    fn polygon_with_too_few_points_is_empty() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 0.0),
+        ];
+
+        assert!(convex_polygon_quads(DrawMode::Fill, &points).is_empty());
+        assert!(convex_polygon_quads(DrawMode::Stroke(1.0), &points).is_empty());
+    }
+
+    #[test]
+    fn polygon_stroke_is_closed() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(8.0, 0.0),
+            Vec2::new(0.0, 8.0),
+        ];
+        let stroke = convex_polygon_quads(DrawMode::Stroke(2.0), &points);
+
+        assert_eq!(stroke.len(), 3);
+
+        // Midpoint of the edge joining the last point back to the first.
+        assert!(covers(&stroke, Vec2::new(0.0, 4.0)));
+        assert!(!covers(&stroke, Vec2::new(2.5, 2.5)));
+    }
+}
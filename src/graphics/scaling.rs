@@ -1,11 +1,12 @@
 //! Functions and types relating to screen scaling.
 
-use crate::Context;
 use crate::error::Result;
-use crate::graphics::{self, Canvas, DrawParams, Rectangle};
+use crate::graphics::{self, Canvas, Color, DrawMode, DrawParams, PolygonMode, Rectangle};
 use crate::input;
+use crate::math::Mat4;
 use crate::math::Vec2;
 use crate::window;
+use crate::Context;
 
 /// A wrapper for a [`Canvas`] that handles scaling the image to fit the screen.
 ///
@@ -72,7 +73,16 @@ impl ScreenScaler {
     }
 
     /// Draws the scaled image to the screen.
+    ///
+    /// If a [letterbox color](graphics::set_letterbox_color) has been set, any area of the
+    /// outer bounds that is not covered by the scaled image will also be filled.
     pub fn draw(&self, ctx: &mut Context) {
+        if let Some(color) = graphics::get_letterbox_color(ctx) {
+            for bar in letterbox_bars(self.screen_rect, self.outer_width, self.outer_height) {
+                graphics::draw_rectangle(ctx, DrawMode::Fill, bar, color);
+            }
+        }
+
         graphics::set_texture(ctx, &self.canvas.texture);
 
         graphics::push_quad(
@@ -119,6 +129,14 @@ impl ScreenScaler {
         (self.inner_width, self.inner_height)
     }
 
+    /// Returns the region of the outer bounds that the scaled image is drawn to.
+    ///
+    /// Any area outside of this rectangle (but inside the outer size) is part of the
+    /// letterbox bars.
+    pub fn screen_rect(&self) -> Rectangle {
+        self.screen_rect
+    }
+
     /// Returns the optimal scale factor for the current `ScalingMode` and configured sizes.  
     /// This can be used for simple use cases where scaling by [canvas](Self::canvas) is not
     /// feasible (e.g. 3rd party UI libraries).
@@ -227,6 +245,107 @@ impl ScreenScaler {
     }
 }
 
+/// Calculates the areas of the outer bounds that are not covered by the screen rectangle.
+///
+/// The top and bottom bars span the full width of the outer bounds, while the left and right
+/// bars fill the space in between them.
+fn letterbox_bars(screen_rect: Rectangle, outer_width: i32, outer_height: i32) -> Vec<Rectangle> {
+    let outer_width = outer_width as f32;
+    let outer_height = outer_height as f32;
+
+    let top = screen_rect.y.clamp(0.0, outer_height);
+    let bottom = (screen_rect.y + screen_rect.height).clamp(top, outer_height);
+    let left = screen_rect.x.clamp(0.0, outer_width);
+    let right = (screen_rect.x + screen_rect.width).clamp(left, outer_width);
+
+    let bars = [
+        Rectangle::new(0.0, 0.0, outer_width, top),
+        Rectangle::new(0.0, bottom, outer_width, outer_height - bottom),
+        Rectangle::new(0.0, top, left, bottom - top),
+        Rectangle::new(right, top, outer_width - right, bottom - top),
+    ];
+
+    bars.into_iter()
+        .filter(|r| r.width > 0.0 && r.height > 0.0)
+        .collect()
+}
+
+/// Returns a reference to the [`ScreenScaler`] that is being used to automatically scale the
+/// screen, or [`None`] if [`ContextBuilder::auto_scale`](crate::ContextBuilder::auto_scale)
+/// is not enabled.
+///
+/// Note that when auto scaling is enabled, [`input::get_mouse_position`] will already return
+/// logical co-ordinates, so there is no need to call [`ScreenScaler::mouse_position`].
+pub fn get_auto_scaler(ctx: &Context) -> Option<&ScreenScaler> {
+    ctx.screen_scaler.as_ref()
+}
+
+/// Returns a mutable reference to the [`ScreenScaler`] that is being used to automatically
+/// scale the screen, or [`None`] if
+/// [`ContextBuilder::auto_scale`](crate::ContextBuilder::auto_scale) is not enabled.
+///
+/// This can be used to change the scaling mode at runtime.
+pub fn get_auto_scaler_mut(ctx: &mut Context) -> Option<&mut ScreenScaler> {
+    ctx.screen_scaler.as_mut()
+}
+
+/// Redirects drawing to the auto scaler's canvas, before the game is drawn.
+pub(crate) fn begin_auto_scale(ctx: &mut Context) {
+    if let Some(canvas) = auto_scale_canvas(ctx) {
+        graphics::set_canvas(ctx, &canvas);
+    }
+}
+
+/// Draws the auto scaler's canvas to the screen, after the game is drawn.
+///
+/// The render state that the game left behind is restored afterwards, so that it can
+/// carry on into the next frame as it would without auto scaling.
+pub(crate) fn end_auto_scale(ctx: &mut Context) {
+    if ctx.screen_scaler.is_none() {
+        return;
+    }
+
+    let shader = ctx.graphics.shader.clone();
+    let transform = graphics::get_transform_matrix(ctx);
+    let blend_state = ctx.graphics.blend_state;
+    let polygon_mode = ctx.graphics.polygon_mode;
+
+    // This has to happen while the scaler is still in the context, so that the logical
+    // screen's scissor rectangle gets saved.
+    graphics::set_canvas_ex(ctx, None);
+
+    let scaler = ctx
+        .screen_scaler
+        .take()
+        .expect("auto scaler should still be present");
+
+    graphics::reset_shader(ctx);
+    graphics::set_transform_matrix(ctx, Mat4::identity());
+    graphics::reset_blend_state(ctx);
+
+    if polygon_mode != PolygonMode::Fill {
+        graphics::set_polygon_mode(ctx, PolygonMode::Fill);
+    }
+
+    graphics::clear(ctx, Color::BLACK);
+    scaler.draw(ctx);
+
+    if polygon_mode != PolygonMode::Fill {
+        graphics::set_polygon_mode(ctx, polygon_mode);
+    }
+
+    graphics::set_blend_state(ctx, blend_state);
+    graphics::set_transform_matrix(ctx, transform);
+    graphics::set_shader_ex(ctx, shader.as_ref());
+
+    ctx.screen_scaler = Some(scaler);
+}
+
+/// Returns the canvas that stands in for the screen when auto scaling is enabled.
+pub(crate) fn auto_scale_canvas(ctx: &Context) -> Option<Canvas> {
+    ctx.screen_scaler.as_ref().map(|s| s.canvas().clone())
+}
+
 fn project_impl(window_pos: f32, rect_pos: f32, rect_size: f32, real_size: f32) -> f32 {
     (real_size * (window_pos - rect_pos)) / rect_size
 }
@@ -236,8 +355,17 @@ fn unproject_impl(screen_pos: f32, rect_pos: f32, rect_size: f32, real_size: f32
 }
 
 /// Algorithms that can be used to scale the game's screen.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
 #[non_exhaustive]
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub enum ScalingMode {
     /// The game will always be displayed at its native resolution, with no scaling applied.
     /// If the window is bigger than the native resolution, letterboxing will be applied.
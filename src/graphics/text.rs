@@ -176,6 +176,15 @@ impl Debug for Font {
 /// the same from frame to frame, reusing the `Text` object will be much
 /// faster than recreating it.
 ///
+/// Text is drawn via the same sprite batch as textures, with each glyph being pushed as a
+/// quad. All of the glyphs for a [`Font`] (and its clones) are stored in a single texture
+/// on the GPU, so drawing multiple pieces of text with the same font will not cause any
+/// extra draw calls. However, the batch has to be [flushed](crate::graphics::flush) whenever
+/// the texture changes - if you are drawing a HUD that mixes text and sprites, drawing
+/// all of the text together (rather than interleaving it with other textures) will
+/// minimize the number of draw calls. You can check how many draw calls are being made
+/// via [`time::get_frame_stats`](crate::time::get_frame_stats).
+///
 /// # Examples
 ///
 /// The [`text`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/text.rs)
@@ -227,7 +236,11 @@ impl Text {
     {
         self.update_geometry(ctx);
 
-        let params = params.into();
+        let mut params = params.into();
+
+        if let Some(bounds) = self.geometry.as_ref().and_then(|g| g.bounds) {
+            params.resolve_anchor_in(bounds.x, bounds.y, bounds.width, bounds.height);
+        }
 
         let data = self.font.data.borrow();
         let texture = data.texture();
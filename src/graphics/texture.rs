@@ -13,12 +13,14 @@ use crate::Context;
 pub(crate) struct TextureSharedData {
     pub(crate) handle: RawTexture,
     filter_mode: Cell<FilterMode>,
+    wrap_mode: Cell<WrapMode>,
+    has_mipmaps: Cell<bool>,
 }
 
 impl PartialEq for TextureSharedData {
     fn eq(&self, other: &TextureSharedData) -> bool {
-        // filter_mode should always match what's set on the GPU,
-        // so we can ignore it for equality checks.
+        // filter_mode, wrap_mode and has_mipmaps should always match what's set on the GPU,
+        // so we can ignore them for equality checks.
 
         self.handle.eq(&other.handle)
     }
@@ -109,14 +111,21 @@ impl Texture {
         format: TextureFormat,
         data: &[u8],
     ) -> Result<Texture> {
-        Texture::with_device(
+        let mut texture = Texture::with_device(
             &mut ctx.device,
             width,
             height,
             data,
             format,
             ctx.graphics.default_filter_mode,
-        )
+        )?;
+
+        // Empty textures have nothing to generate mipmaps from.
+        if ctx.graphics.generate_mipmaps && width > 0 && height > 0 {
+            texture.generate_mipmaps(ctx)?;
+        }
+
+        Ok(texture)
     }
 
     /// Creates a new texture from a slice of data, encoded in one of Tetra's supported
@@ -130,12 +139,26 @@ impl Texture {
     /// might have to be added later. Note that TGA files do not have recognizable magic
     /// bytes, so this function will not recognize them.
     ///
+    /// Only formats that have been enabled via Cargo features (e.g. `texture_png` and
+    /// `texture_jpeg`, which are on by default) can be decoded.
+    ///
     /// # Errors
     ///
     /// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be
     /// returned if the underlying graphics API encounters an error.
     /// * [`TetraError::InvalidTexture`](crate::TetraError::InvalidTexture) will be
     /// returned if the texture data was invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use tetra::Context;
+    /// # use tetra::graphics::Texture;
+    /// # fn load(ctx: &mut Context) -> tetra::Result {
+    /// let texture = Texture::from_encoded(ctx, include_bytes!("../../examples/resources/player.png"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn from_encoded(ctx: &mut Context, data: &[u8]) -> Result<Texture> {
         let data = ImageData::from_encoded(data)?;
         Texture::from_image_data(ctx, &data)
@@ -162,6 +185,8 @@ impl Texture {
             data: Rc::new(TextureSharedData {
                 handle,
                 filter_mode: Cell::new(filter_mode),
+                wrap_mode: Cell::new(WrapMode::Clamp),
+                has_mipmaps: Cell::new(false),
             }),
         }
     }
@@ -182,6 +207,8 @@ impl Texture {
             data: Rc::new(TextureSharedData {
                 handle,
                 filter_mode: Cell::new(filter_mode),
+                wrap_mode: Cell::new(WrapMode::Clamp),
+                has_mipmaps: Cell::new(false),
             }),
         })
     }
@@ -211,7 +238,8 @@ impl Texture {
     where
         P: Into<DrawParams>,
     {
-        let params = params.into();
+        let mut params = params.into();
+        params.resolve_anchor(self.width() as f32, self.height() as f32);
 
         graphics::set_texture(ctx, self);
         graphics::push_quad(
@@ -233,7 +261,8 @@ impl Texture {
     where
         P: Into<DrawParams>,
     {
-        let params = params.into();
+        let mut params = params.into();
+        params.resolve_anchor(region.width, region.height);
 
         let texture_width = self.width() as f32;
         let texture_height = self.height() as f32;
@@ -265,7 +294,8 @@ impl Texture {
     ) where
         P: Into<DrawParams>,
     {
-        let params = params.into();
+        let mut params = params.into();
+        params.resolve_anchor(width, height);
 
         let texture_width = self.width() as f32;
         let texture_height = self.height() as f32;
@@ -344,13 +374,83 @@ impl Texture {
     }
 
     /// Sets the filter mode that should be used by the texture.
+    ///
+    /// If the texture has [mipmaps](Texture::generate_mipmaps), they will also be
+    /// filtered using this mode.
     pub fn set_filter_mode(&mut self, ctx: &mut Context, filter_mode: FilterMode) {
-        ctx.device
-            .set_texture_filter_mode(&self.data.handle, filter_mode);
+        ctx.device.set_texture_filter_mode(
+            &self.data.handle,
+            filter_mode,
+            self.data.has_mipmaps.get(),
+        );
 
         self.data.filter_mode.set(filter_mode);
     }
 
+    /// Generates mipmaps for the texture.
+    ///
+    /// Mipmaps are pre-calculated, progressively smaller versions of a texture, which will
+    /// be sampled from when the texture is drawn at a smaller size than its original
+    /// dimensions. This can greatly reduce aliasing when drawing scaled down art (e.g. a
+    /// zoomed out map), especially when combined with [`FilterMode::Linear`].
+    ///
+    /// Note that mipmaps take up roughly a third more GPU memory than the texture itself.
+    /// Once a texture has mipmaps, they will be regenerated every time its data is changed
+    /// via [`set_data`](Texture::set_data) or [`replace_data`](Texture::replace_data), so
+    /// avoid them for textures that are updated every frame.
+    ///
+    /// Mipmaps can be generated automatically for all newly loaded textures by setting
+    /// [`ContextBuilder::generate_mipmaps`](crate::ContextBuilder::generate_mipmaps).
+    ///
+    /// # Errors
+    ///
+    /// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned
+    ///   if the texture's dimensions are not supported by the graphics hardware, or if the
+    ///   underlying graphics API encounters an error.
+    pub fn generate_mipmaps(&mut self, ctx: &mut Context) -> Result {
+        self.generate_mipmaps_impl(ctx)
+    }
+
+    fn generate_mipmaps_impl(&self, ctx: &mut Context) -> Result {
+        // The batch may contain quads that were drawn before the mipmaps existed.
+        graphics::flush(ctx);
+
+        ctx.device
+            .generate_mipmaps(&self.data.handle, self.data.filter_mode.get())?;
+
+        self.data.has_mipmaps.set(true);
+
+        Ok(())
+    }
+
+    /// Returns whether or not mipmaps have been generated for the texture.
+    pub fn has_mipmaps(&self) -> bool {
+        self.data.has_mipmaps.get()
+    }
+
+    /// Returns the wrap mode being used by the texture.
+    pub fn wrap_mode(&self) -> WrapMode {
+        self.data.wrap_mode.get()
+    }
+
+    /// Sets the wrap mode that should be used by the texture.
+    ///
+    /// This determines what happens when the texture is sampled outside of
+    /// its bounds - for example, when drawing it via [`graphics::draw_tiled`].
+    pub fn set_wrap_mode(&mut self, ctx: &mut Context, wrap_mode: WrapMode) {
+        ctx.device
+            .set_texture_wrap_mode(&self.data.handle, wrap_mode);
+
+        self.data.wrap_mode.set(wrap_mode);
+    }
+
+    /// Temporarily overrides the wrap mode on the GPU, without changing the stored setting.
+    ///
+    /// The caller is responsible for restoring the stored wrap mode afterwards.
+    pub(crate) fn override_wrap_mode(&self, device: &mut GraphicsDevice, wrap_mode: WrapMode) {
+        device.set_texture_wrap_mode(&self.data.handle, wrap_mode);
+    }
+
     /// Gets the texture's data from the GPU.
     ///
     /// This can be useful if you need to do some image processing on the CPU,
@@ -379,11 +479,16 @@ impl Texture {
     /// If you want to overwrite the entire texture, the [`replace_data`](Self::replace_data)
     /// method offers a more concise way of doing this.
     ///
+    /// If the texture has [mipmaps](Texture::generate_mipmaps), they will be regenerated
+    /// to match the new data.
+    ///
     /// # Errors
     ///
     /// * [`TetraError::NotEnoughData`](crate::TetraError::NotEnoughData) will be returned if
     /// not enough data is provided to fill the target rectangle. This is to prevent the
     /// graphics API from trying to read uninitialized memory.
+    /// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned
+    ///   if the texture's mipmaps could not be regenerated.
     ///
     /// # Panics
     ///
@@ -398,7 +503,13 @@ impl Texture {
         data: &[u8],
     ) -> Result {
         ctx.device
-            .set_texture_data(&self.data.handle, data, x, y, width, height)
+            .set_texture_data(&self.data.handle, data, x, y, width, height)?;
+
+        if self.data.has_mipmaps.get() {
+            self.generate_mipmaps_impl(ctx)?;
+        }
+
+        Ok(())
     }
 
     /// Overwrites the entire texture with new RGBA pixel data.
@@ -410,11 +521,16 @@ impl Texture {
     /// If you only want to write to a subsection of the texture, use the [`set_data`](Self::set_data)
     /// method instead.
     ///
+    /// If the texture has [mipmaps](Texture::generate_mipmaps), they will be regenerated
+    /// to match the new data.
+    ///
     /// # Errors
     ///
     /// * [`TetraError::NotEnoughData`](crate::TetraError::NotEnoughData) will be returned if not
     /// enough data is provided to fill the texture. This is to prevent the graphics API from
     /// trying to read uninitialized memory.
+    /// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned
+    ///   if the texture's mipmaps could not be regenerated.
     pub fn replace_data(&self, ctx: &mut Context, data: &[u8]) -> Result {
         let (width, height) = self.size();
         self.set_data(ctx, 0, 0, width, height, data)
@@ -467,6 +583,22 @@ pub enum FilterMode {
     Linear,
 }
 
+/// Algorithms that can be used when a texture is sampled outside of its bounds.
+///
+/// Tetra currently defaults to using `Clamp` for all newly created textures.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    /// The edge pixels of the texture will be stretched to fill the space.
+    Clamp,
+
+    /// The texture will be repeated.
+    Repeat,
+
+    /// The texture will be repeated, with every other repetition being mirrored.
+    MirroredRepeat,
+}
+
 /// Information on how to slice a texture so that it can be stretched or squashed without
 /// distorting the borders.
 ///
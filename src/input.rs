@@ -9,6 +9,14 @@
 //! a new one will be allocated. This means that if you unplug a controller and then plug it back in,
 //! it should retain its existing ID. This behaviour might be made smarter in future versions.
 //!
+//! # Joysticks
+//!
+//! Devices that are not supported by the gamepad API (e.g. flight sticks and arcade panels) can
+//! be accessed via the joystick API instead, which exposes their axes, buttons and hat switches
+//! by index, without any mapping applied. Joysticks must be opened via [`open_joystick`] before
+//! they can be used - an [`Event::JoystickAdded`](crate::Event::JoystickAdded) event will be fired
+//! whenever a new device is connected.
+//!
 //! # Examples
 //!
 //! The [`keyboard`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/keyboard.rs)
@@ -24,63 +32,73 @@
 //! example demonstrates how to handle text entry.
 
 mod gamepad;
+mod joystick;
 mod keyboard;
 mod mouse;
 
+use std::time::Duration;
+
 use hashbrown::HashSet;
 
 use crate::math::Vec2;
 use crate::{Context, Result};
 
 pub use gamepad::*;
+pub use joystick::*;
 pub use keyboard::*;
 pub use mouse::*;
 
 pub(crate) struct InputContext {
-    keys_down: HashSet<Key>,
-    keys_pressed: HashSet<Key>,
-    keys_released: HashSet<Key>,
+    keys: KeyState,
 
     key_modifier_state: KeyModifierState,
 
     mouse_buttons_down: HashSet<MouseButton>,
     mouse_buttons_pressed: HashSet<MouseButton>,
     mouse_buttons_released: HashSet<MouseButton>,
+    mouse_buttons_double_clicked: HashSet<MouseButton>,
     mouse_position: Vec2<f32>,
     mouse_wheel_movement: Vec2<i32>,
+    click_tracker: ClickTracker,
+    double_click_time: Duration,
 
     current_text_input: Option<String>,
 
     pads: Vec<Option<GamepadState>>,
+    gamepad_deadzone: f32,
+    joysticks: Vec<Option<JoystickState>>,
 }
 
 impl InputContext {
     pub(crate) fn new() -> InputContext {
         InputContext {
-            keys_down: HashSet::new(),
-            keys_pressed: HashSet::new(),
-            keys_released: HashSet::new(),
+            keys: KeyState::default(),
 
             key_modifier_state: KeyModifierState::default(),
 
             mouse_buttons_down: HashSet::new(),
             mouse_buttons_pressed: HashSet::new(),
             mouse_buttons_released: HashSet::new(),
+            mouse_buttons_double_clicked: HashSet::new(),
             mouse_position: Vec2::zero(),
             mouse_wheel_movement: Vec2::zero(),
+            click_tracker: ClickTracker::default(),
+            double_click_time: Duration::from_millis(500),
 
             current_text_input: None,
 
             pads: Vec::new(),
+            gamepad_deadzone: 0.0,
+            joysticks: Vec::new(),
         }
     }
 }
 
 pub(crate) fn clear(ctx: &mut Context) {
-    ctx.input.keys_pressed.clear();
-    ctx.input.keys_released.clear();
+    ctx.input.keys.clear();
     ctx.input.mouse_buttons_pressed.clear();
     ctx.input.mouse_buttons_released.clear();
+    ctx.input.mouse_buttons_double_clicked.clear();
     ctx.input.mouse_wheel_movement = Vec2::zero();
 
     ctx.input.current_text_input = None;
@@ -89,14 +107,40 @@ pub(crate) fn clear(ctx: &mut Context) {
         pad.buttons_pressed.clear();
         pad.buttons_released.clear();
     }
+
+    for joystick in ctx.input.joysticks.iter_mut().flatten() {
+        joystick.buttons_pressed.clear();
+        joystick.buttons_released.clear();
+    }
 }
 
 /// Returns the text that the user entered since the last update.
 /// This will match the user's keyboard and OS settings.
+///
+/// Text will only be received while text input is enabled - see
+/// [`set_text_input_enabled`] for more information.
 pub fn get_text_input(ctx: &Context) -> Option<&str> {
     ctx.input.current_text_input.as_deref()
 }
 
+/// Sets whether or not text input is enabled.
+///
+/// While text input is enabled, [`get_text_input`] and
+/// [`Event::TextInput`](crate::Event::TextInput) will receive the text that the user
+/// types, and the platform's input method editor (IME) may be shown on some systems
+/// (e.g. an on-screen keyboard). You may want to only enable it while a text box
+/// is focused.
+///
+/// Text input is enabled by default on desktop platforms.
+pub fn set_text_input_enabled(ctx: &mut Context, text_input_enabled: bool) {
+    ctx.window.set_text_input_enabled(text_input_enabled);
+}
+
+/// Returns true if text input is currently enabled.
+pub fn is_text_input_enabled(ctx: &Context) -> bool {
+    ctx.window.is_text_input_enabled()
+}
+
 /// Gets the text currently stored in the system's clipboard.
 ///
 /// # Errors
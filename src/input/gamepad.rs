@@ -1,7 +1,7 @@
 use hashbrown::{HashMap, HashSet};
 
 use crate::math::Vec2;
-use crate::Context;
+use crate::{Context, Result};
 
 pub(crate) struct GamepadState {
     pub platform_id: u32,
@@ -120,6 +120,50 @@ pub enum GamepadStick {
     RightStick,
 }
 
+/// The type of a gamepad, as reported by the platform.
+///
+/// This can be used to display the correct button prompts for the player's controller.
+/// Note that [`GamepadButton`] always refers to the *position* of a button, rather than
+/// its label - for example, [`GamepadButton::A`] is the bottom face button, which is
+/// labelled 'A' on an Xbox controller, 'Cross' on a PlayStation controller, and 'B' on
+/// a Nintendo Switch Pro controller.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum GamepadType {
+    /// The type of the gamepad could not be determined.
+    Unknown,
+
+    /// An Xbox 360 controller.
+    Xbox360,
+
+    /// An Xbox One (or Xbox Series) controller.
+    XboxOne,
+
+    /// A PlayStation 3 controller.
+    PlayStation3,
+
+    /// A PlayStation 4 controller.
+    PlayStation4,
+
+    /// A PlayStation 5 controller.
+    PlayStation5,
+
+    /// A Nintendo Switch Pro controller.
+    NintendoSwitchPro,
+
+    /// A virtual controller, emulated by software.
+    Virtual,
+}
+
 /// Returns true if the specified gamepad is currently connected.
 pub fn is_gamepad_connected(ctx: &Context, gamepad_id: usize) -> bool {
     get_gamepad(ctx, gamepad_id).is_some()
@@ -132,6 +176,30 @@ pub fn get_gamepad_name(ctx: &Context, gamepad_id: usize) -> Option<String> {
         .map(|id| ctx.window.get_gamepad_name(id))
 }
 
+/// Returns the type of the specified gamepad, or [`None`] if it is not connected.
+pub fn get_gamepad_type(ctx: &Context, gamepad_id: usize) -> Option<GamepadType> {
+    get_gamepad(ctx, gamepad_id)
+        .map(|g| g.platform_id)
+        .map(|id| ctx.window.get_gamepad_type(id))
+}
+
+/// Adds gamepad mappings from a string, in the format used by SDL's game controller
+/// database (e.g. [`gamecontrollerdb.txt`](https://github.com/gabomdq/SDL_GameControllerDB)),
+/// and returns the number of mappings that were added.
+///
+/// Each line of the string should contain a single mapping. Mappings for other platforms
+/// will be ignored. This makes it possible to support devices that are not recognized as
+/// gamepads by default - for best results, call this at startup, before any gamepads
+/// have been connected.
+///
+/// # Errors
+///
+/// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned
+///   if the mappings could not be parsed.
+pub fn add_gamepad_mappings(ctx: &mut Context, mappings: &str) -> Result<u32> {
+    ctx.window.add_gamepad_mappings(mappings)
+}
+
 /// Returns true if the specified gamepad button is currently down.
 ///
 /// If the gamepad is disconnected, this will always return `false`.
@@ -246,21 +314,23 @@ pub fn get_gamepad_buttons_released(
 
 /// Returns the current position of the specified gamepad axis.
 ///
+/// The [deadzone](set_gamepad_deadzone) will be applied to the returned value.
+///
 /// If the gamepad is disconnected, this will always return `0.0`.
 pub fn get_gamepad_axis_position(ctx: &Context, gamepad_id: usize, axis: GamepadAxis) -> f32 {
-    if let Some(pad) = get_gamepad(ctx, gamepad_id) {
-        if let Some(value) = pad.current_axis_state.get(&axis) {
-            *value
-        } else {
-            0.0
-        }
-    } else {
-        0.0
-    }
+    apply_axis_deadzone(
+        get_raw_axis_position(ctx, gamepad_id, axis),
+        ctx.input.gamepad_deadzone,
+    )
 }
 
 /// Returns the current position of the specified gamepad control stick.
 ///
+/// The [deadzone](set_gamepad_deadzone) will be applied to the returned value. Unlike
+/// [`get_gamepad_axis_position`], this is calculated based on the distance of the stick
+/// from the center, rather than on each axis individually - this avoids the stick
+/// 'snapping' to the X and Y axes when it is moved diagonally.
+///
 /// If the gamepad is disconnected, this will always return `(0.0, 0.0)`.
 pub fn get_gamepad_stick_position(
     ctx: &Context,
@@ -272,10 +342,58 @@ pub fn get_gamepad_stick_position(
         GamepadStick::RightStick => (GamepadAxis::RightStickX, GamepadAxis::RightStickY),
     };
 
-    Vec2::new(
-        get_gamepad_axis_position(ctx, gamepad_id, x_axis),
-        get_gamepad_axis_position(ctx, gamepad_id, y_axis),
-    )
+    let position = Vec2::new(
+        get_raw_axis_position(ctx, gamepad_id, x_axis),
+        get_raw_axis_position(ctx, gamepad_id, y_axis),
+    );
+
+    apply_stick_deadzone(position, ctx.input.gamepad_deadzone)
+}
+
+/// Returns the deadzone that is applied to gamepad axes and sticks.
+pub fn get_gamepad_deadzone(ctx: &Context) -> f32 {
+    ctx.input.gamepad_deadzone
+}
+
+/// Sets the deadzone that is applied to gamepad axes and sticks, between `0.0` and `1.0`.
+///
+/// Positions within the deadzone will be reported as `0.0`, and positions outside of it
+/// will be rescaled so that they still cover the full range of motion. This can be used
+/// to prevent worn or loose sticks from registering movement while at rest.
+///
+/// This applies to all gamepads, and affects [`get_gamepad_axis_position`] and
+/// [`get_gamepad_stick_position`]. The values in
+/// [`Event::GamepadAxisMoved`](crate::Event::GamepadAxisMoved) and
+/// [`Event::GamepadStickMoved`](crate::Event::GamepadStickMoved) are not affected.
+///
+/// Defaults to `0.0`.
+pub fn set_gamepad_deadzone(ctx: &mut Context, deadzone: f32) {
+    ctx.input.gamepad_deadzone = deadzone.clamp(0.0, 1.0);
+}
+
+fn get_raw_axis_position(ctx: &Context, gamepad_id: usize, axis: GamepadAxis) -> f32 {
+    get_gamepad(ctx, gamepad_id)
+        .and_then(|pad| pad.current_axis_state.get(&axis).copied())
+        .unwrap_or(0.0)
+}
+
+fn apply_axis_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value.abs() <= deadzone {
+        0.0
+    } else {
+        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+    }
+}
+
+fn apply_stick_deadzone(position: Vec2<f32>, deadzone: f32) -> Vec2<f32> {
+    let magnitude = position.magnitude();
+
+    if magnitude <= deadzone {
+        Vec2::zero()
+    } else {
+        let scaled = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0);
+        position * (scaled / magnitude)
+    }
 }
 
 /// Returns true if the specified gamepad supports vibration.
@@ -345,3 +463,37 @@ pub(crate) fn get_gamepad_mut(ctx: &mut Context, gamepad_id: usize) -> Option<&m
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_deadzone() {
+        assert_eq!(0.0, apply_axis_deadzone(0.25, 0.5));
+        assert_eq!(0.0, apply_axis_deadzone(-0.5, 0.5));
+        assert_eq!(0.5, apply_axis_deadzone(0.75, 0.5));
+        assert_eq!(-1.0, apply_axis_deadzone(-1.0, 0.5));
+    }
+
+    #[test]
+    fn stick_deadzone_is_radial() {
+        assert_eq!(Vec2::zero(), apply_stick_deadzone(Vec2::new(0.1, 0.1), 0.2));
+
+        // Diagonal movement outside of the deadzone should not snap to an axis.
+        let position = apply_stick_deadzone(Vec2::new(0.15, 0.15), 0.2);
+        assert!(position.x > 0.0 && position.y > 0.0);
+
+        let position = apply_stick_deadzone(Vec2::new(1.0, 0.0), 0.5);
+        assert_eq!(Vec2::new(1.0, 0.0), position);
+    }
+
+    #[test]
+    fn zero_deadzone_is_passthrough() {
+        assert_eq!(0.3, apply_axis_deadzone(0.3, 0.0));
+        assert_eq!(
+            Vec2::new(0.3, -0.4),
+            apply_stick_deadzone(Vec2::new(0.3, -0.4), 0.0)
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use hashbrown::HashSet;
+
+use crate::{Context, Result};
+
+pub(crate) struct JoystickState {
+    pub platform_id: u32,
+    pub button_count: u32,
+    pub buttons_down: HashSet<u32>,
+    pub buttons_pressed: HashSet<u32>,
+    pub buttons_released: HashSet<u32>,
+    pub axes: Vec<f32>,
+    pub hats: Vec<JoystickHat>,
+}
+
+impl JoystickState {
+    pub(crate) fn new(
+        platform_id: u32,
+        axis_count: u32,
+        button_count: u32,
+        hat_count: u32,
+    ) -> JoystickState {
+        JoystickState {
+            platform_id,
+            button_count,
+            buttons_down: HashSet::new(),
+            buttons_pressed: HashSet::new(),
+            buttons_released: HashSet::new(),
+            axes: vec![0.0; axis_count as usize],
+            hats: vec![JoystickHat::Centered; hat_count as usize],
+        }
+    }
+
+    pub(crate) fn set_button_down(&mut self, button: u32) -> bool {
+        let was_up = self.buttons_down.insert(button);
+
+        if was_up {
+            self.buttons_pressed.insert(button);
+        }
+
+        was_up
+    }
+
+    pub(crate) fn set_button_up(&mut self, button: u32) -> bool {
+        let was_down = self.buttons_down.remove(&button);
+
+        if was_down {
+            self.buttons_released.insert(button);
+        }
+
+        was_down
+    }
+
+    pub(crate) fn set_axis_position(&mut self, axis: u32, value: f32) {
+        if let Some(a) = self.axes.get_mut(axis as usize) {
+            *a = value;
+        }
+    }
+
+    pub(crate) fn set_hat_position(&mut self, hat: u32, position: JoystickHat) {
+        if let Some(h) = self.hats.get_mut(hat as usize) {
+            *h = position;
+        }
+    }
+}
+
+/// The position of a hat switch (sometimes called a POV hat) on a joystick.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[allow(missing_docs)]
+pub enum JoystickHat {
+    Centered,
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// Returns the number of joystick devices that are currently attached to the system.
+///
+/// This includes devices that have not been opened via [`open_joystick`], as well as
+/// devices that are also available via the gamepad API.
+///
+/// # Errors
+///
+/// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned
+///   if the number of devices could not be determined.
+pub fn get_joystick_device_count(ctx: &Context) -> Result<u32> {
+    ctx.window.get_joystick_device_count()
+}
+
+/// Returns the name of the joystick device at the specified index.
+///
+/// Device indices range from zero to [`get_joystick_device_count`], and may change
+/// when devices are connected or disconnected.
+///
+/// # Errors
+///
+/// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned
+///   if the device index is invalid.
+pub fn get_joystick_device_name(ctx: &Context, device_index: u32) -> Result<String> {
+    ctx.window.get_joystick_device_name(device_index)
+}
+
+/// Opens the joystick device at the specified index, and returns the ID that can be used
+/// to access its state.
+///
+/// Unlike gamepads, joysticks are not opened automatically - you will receive an
+/// [`Event::JoystickAdded`](crate::Event::JoystickAdded) event when one is connected, which
+/// contains the device index that should be passed to this function. Joystick state is
+/// not mapped in any way, making it suitable for devices that are not supported by the
+/// gamepad API (e.g. flight sticks and arcade panels).
+///
+/// If the device has already been opened, the existing ID will be returned.
+///
+/// # Errors
+///
+/// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned
+///   if the device could not be opened.
+pub fn open_joystick(ctx: &mut Context, device_index: u32) -> Result<usize> {
+    let state = ctx.window.open_joystick(device_index)?;
+
+    if let Some(id) = find_joystick(ctx, state.platform_id) {
+        return Ok(id);
+    }
+
+    for (i, slot) in ctx.input.joysticks.iter_mut().enumerate() {
+        if slot.is_none() {
+            *slot = Some(state);
+            return Ok(i);
+        }
+    }
+
+    // There wasn't an existing free slot...
+    let i = ctx.input.joysticks.len();
+    ctx.input.joysticks.push(Some(state));
+    Ok(i)
+}
+
+/// Closes the specified joystick.
+///
+/// The ID may be reused the next time a joystick is opened.
+pub fn close_joystick(ctx: &mut Context, joystick_id: usize) {
+    if let Some(platform_id) = get_joystick(ctx, joystick_id).map(|j| j.platform_id) {
+        ctx.window.close_joystick(platform_id);
+        remove_joystick(ctx, joystick_id);
+    }
+}
+
+/// Returns true if the specified joystick is currently open and connected.
+pub fn is_joystick_connected(ctx: &Context, joystick_id: usize) -> bool {
+    get_joystick(ctx, joystick_id).is_some()
+}
+
+/// Returns the name of the specified joystick, or [`None`] if it is not connected.
+pub fn get_joystick_name(ctx: &Context, joystick_id: usize) -> Option<String> {
+    get_joystick(ctx, joystick_id).map(|j| ctx.window.get_joystick_name(j.platform_id))
+}
+
+/// Returns the number of axes on the specified joystick.
+///
+/// If the joystick is disconnected, this will always return `0`.
+pub fn get_joystick_axis_count(ctx: &Context, joystick_id: usize) -> u32 {
+    get_joystick(ctx, joystick_id).map_or(0, |j| j.axes.len() as u32)
+}
+
+/// Returns the number of buttons on the specified joystick.
+///
+/// If the joystick is disconnected, this will always return `0`.
+pub fn get_joystick_button_count(ctx: &Context, joystick_id: usize) -> u32 {
+    get_joystick(ctx, joystick_id).map_or(0, |j| j.button_count)
+}
+
+/// Returns the number of hat switches on the specified joystick.
+///
+/// If the joystick is disconnected, this will always return `0`.
+pub fn get_joystick_hat_count(ctx: &Context, joystick_id: usize) -> u32 {
+    get_joystick(ctx, joystick_id).map_or(0, |j| j.hats.len() as u32)
+}
+
+/// Returns true if the specified joystick button is currently down.
+///
+/// If the joystick is disconnected, this will always return `false`.
+pub fn is_joystick_button_down(ctx: &Context, joystick_id: usize, button: u32) -> bool {
+    matches!(get_joystick(ctx, joystick_id), Some(j) if j.buttons_down.contains(&button))
+}
+
+/// Returns true if the specified joystick button is currently up.
+///
+/// If the joystick is disconnected, this will always return `true`.
+pub fn is_joystick_button_up(ctx: &Context, joystick_id: usize, button: u32) -> bool {
+    !is_joystick_button_down(ctx, joystick_id, button)
+}
+
+/// Returns true if the specified joystick button was pressed since the last update.
+///
+/// If the joystick is disconnected, this will always return `false`.
+pub fn is_joystick_button_pressed(ctx: &Context, joystick_id: usize, button: u32) -> bool {
+    matches!(get_joystick(ctx, joystick_id), Some(j) if j.buttons_pressed.contains(&button))
+}
+
+/// Returns true if the specified joystick button was released since the last update.
+///
+/// If the joystick is disconnected, this will always return `false`.
+pub fn is_joystick_button_released(ctx: &Context, joystick_id: usize, button: u32) -> bool {
+    matches!(get_joystick(ctx, joystick_id), Some(j) if j.buttons_released.contains(&button))
+}
+
+/// Returns the current position of the specified joystick axis, between `-1.0` and `1.0`.
+///
+/// If the joystick is disconnected, or the axis does not exist, this will always return `0.0`.
+pub fn get_joystick_axis_position(ctx: &Context, joystick_id: usize, axis: u32) -> f32 {
+    get_joystick(ctx, joystick_id)
+        .and_then(|j| j.axes.get(axis as usize).copied())
+        .unwrap_or(0.0)
+}
+
+/// Returns the current position of the specified joystick hat switch.
+///
+/// If the joystick is disconnected, or the hat does not exist, this will always return
+/// [`JoystickHat::Centered`].
+pub fn get_joystick_hat_position(ctx: &Context, joystick_id: usize, hat: u32) -> JoystickHat {
+    get_joystick(ctx, joystick_id)
+        .and_then(|j| j.hats.get(hat as usize).copied())
+        .unwrap_or(JoystickHat::Centered)
+}
+
+pub(crate) fn find_joystick(ctx: &Context, platform_id: u32) -> Option<usize> {
+    ctx.input
+        .joysticks
+        .iter()
+        .position(|j| matches!(j, Some(j) if j.platform_id == platform_id))
+}
+
+pub(crate) fn remove_joystick(ctx: &mut Context, joystick_id: usize) {
+    ctx.input.joysticks[joystick_id] = None;
+}
+
+pub(crate) fn get_joystick(ctx: &Context, joystick_id: usize) -> Option<&JoystickState> {
+    if let Some(Some(joystick)) = ctx.input.joysticks.get(joystick_id) {
+        Some(joystick)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn get_joystick_mut(
+    ctx: &mut Context,
+    joystick_id: usize,
+) -> Option<&mut JoystickState> {
+    if let Some(Some(joystick)) = ctx.input.joysticks.get_mut(joystick_id) {
+        Some(joystick)
+    } else {
+        None
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::{self, Display, Formatter};
 
+use hashbrown::HashSet;
+
 use crate::Context;
 
 /// A physical key on a keyboard.
@@ -498,24 +500,92 @@ pub(crate) struct KeyModifierState {
     pub shift: bool,
 }
 
+/// Keeps track of which keys are down, and which have been pressed or released since
+/// the last update.
+#[derive(Debug, Default)]
+pub(crate) struct KeyState {
+    down: HashSet<Key>,
+    pressed: HashSet<Key>,
+    released: HashSet<Key>,
+    pending_release: HashSet<Key>,
+}
+
+impl KeyState {
+    /// Registers a key press, returning whether or not the key was previously up.
+    ///
+    /// If `repeat` is true, the key will be counted as pressed even if it was already down.
+    fn set_down(&mut self, key: Key, repeat: bool) -> bool {
+        // If the key was released and then pressed again before an update could see it,
+        // it was never really up as far as the game is concerned.
+        self.pending_release.remove(&key);
+
+        let was_up = self.down.insert(key);
+
+        if was_up || repeat {
+            self.pressed.insert(key);
+        }
+
+        was_up
+    }
+
+    /// Registers a key release, returning whether or not the key was previously down.
+    fn set_up(&mut self, key: Key) -> bool {
+        // If the press hasn't been seen by an update yet, the release gets deferred until
+        // after the next update, so that the key is reported as down at least once.
+        if self.pressed.contains(&key) {
+            return self.pending_release.insert(key);
+        }
+
+        let was_down = self.down.remove(&key);
+
+        if was_down {
+            self.released.insert(key);
+        }
+
+        was_down
+    }
+
+    /// Clears the pressed and released keys, and applies any deferred releases.
+    pub(crate) fn clear(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+
+        for key in self.pending_release.drain() {
+            self.down.remove(&key);
+            self.released.insert(key);
+        }
+    }
+}
+
 /// Returns true if the specified key is currently down.
+///
+/// If a key is pressed and released again before an update has run, it will still be
+/// reported as down (and [pressed](is_key_pressed)) for the next update, and then
+/// [released](is_key_released) on the update after that. This means that quick taps
+/// are never missed, even at low tick rates.
 pub fn is_key_down(ctx: &Context, key: Key) -> bool {
-    ctx.input.keys_down.contains(&key)
+    ctx.input.keys.down.contains(&key)
 }
 
 /// Returns true if the specified key is currently up.
+///
+/// See [`is_key_down`] for details of how quick taps are handled.
 pub fn is_key_up(ctx: &Context, key: Key) -> bool {
-    !ctx.input.keys_down.contains(&key)
+    !ctx.input.keys.down.contains(&key)
 }
 
 /// Returns true if the specified key was pressed since the last update.
 pub fn is_key_pressed(ctx: &Context, key: Key) -> bool {
-    ctx.input.keys_pressed.contains(&key)
+    ctx.input.keys.pressed.contains(&key)
 }
 
 /// Returns true if the specified key was released since the last update.
+///
+/// If the key was pressed since the last update as well, the release will be reported
+/// one update later, so that the key can be seen as down in between. See
+/// [`is_key_down`] for more information.
 pub fn is_key_released(ctx: &Context, key: Key) -> bool {
-    ctx.input.keys_released.contains(&key)
+    ctx.input.keys.released.contains(&key)
 }
 
 /// Returns true if the specified key modifier is currently down.
@@ -538,17 +608,17 @@ pub fn is_key_modifier_up(ctx: &Context, key_modifier: KeyModifier) -> bool {
 
 /// Returns an iterator of the keys that are currently down.
 pub fn get_keys_down(ctx: &Context) -> impl Iterator<Item = &Key> {
-    ctx.input.keys_down.iter()
+    ctx.input.keys.down.iter()
 }
 
 /// Returns an iterator of the keys that were pressed since the last update.
 pub fn get_keys_pressed(ctx: &Context) -> impl Iterator<Item = &Key> {
-    ctx.input.keys_pressed.iter()
+    ctx.input.keys.pressed.iter()
 }
 
 /// Returns an iterator of the keys that were released since the last update.
 pub fn get_keys_released(ctx: &Context) -> impl Iterator<Item = &Key> {
-    ctx.input.keys_released.iter()
+    ctx.input.keys.released.iter()
 }
 
 /// Returns the key that has the specified label in the current keyboard layout.
@@ -586,25 +656,68 @@ pub fn get_key_label(ctx: &Context, physical_key: Key) -> Option<KeyLabel> {
 }
 
 pub(crate) fn set_key_down(ctx: &mut Context, key: Key) -> bool {
-    let was_up = ctx.input.keys_down.insert(key);
+    let repeat = ctx.window.is_key_repeat_enabled();
+    ctx.input.keys.set_down(key, repeat)
+}
 
-    if was_up || ctx.window.is_key_repeat_enabled() {
-        ctx.input.keys_pressed.insert(key);
-    }
+pub(crate) fn set_key_up(ctx: &mut Context, key: Key) -> bool {
+    ctx.input.keys.set_up(key)
+}
 
-    was_up
+pub(crate) fn set_key_modifier_state(ctx: &mut Context, state: KeyModifierState) {
+    ctx.input.key_modifier_state = state;
 }
 
-pub(crate) fn set_key_up(ctx: &mut Context, key: Key) -> bool {
-    let was_down = ctx.input.keys_down.remove(&key);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn press_and_release_before_update() {
+        let mut keys = KeyState::default();
+
+        keys.set_down(Key::Space, false);
+        keys.set_up(Key::Space);
+
+        // First update: the key is seen as pressed and down, but not yet released.
+        assert!(keys.down.contains(&Key::Space));
+        assert!(keys.pressed.contains(&Key::Space));
+        assert!(!keys.released.contains(&Key::Space));
+
+        keys.clear();
+
+        // Second update: the deferred release is applied.
+        assert!(!keys.down.contains(&Key::Space));
+        assert!(!keys.pressed.contains(&Key::Space));
+        assert!(keys.released.contains(&Key::Space));
 
-    if was_down {
-        ctx.input.keys_released.insert(key);
+        keys.clear();
+
+        assert!(!keys.released.contains(&Key::Space));
     }
 
-    was_down
-}
+    #[test]
+    fn release_after_update_is_immediate() {
+        let mut keys = KeyState::default();
 
-pub(crate) fn set_key_modifier_state(ctx: &mut Context, state: KeyModifierState) {
-    ctx.input.key_modifier_state = state;
+        keys.set_down(Key::Space, false);
+        keys.clear();
+        keys.set_up(Key::Space);
+
+        assert!(!keys.down.contains(&Key::Space));
+        assert!(keys.released.contains(&Key::Space));
+    }
+
+    #[test]
+    fn repress_cancels_deferred_release() {
+        let mut keys = KeyState::default();
+
+        keys.set_down(Key::Space, false);
+        keys.set_up(Key::Space);
+        keys.set_down(Key::Space, false);
+        keys.clear();
+
+        assert!(keys.down.contains(&Key::Space));
+        assert!(!keys.released.contains(&Key::Space));
+    }
 }
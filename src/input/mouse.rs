@@ -1,6 +1,11 @@
+use std::time::{Duration, Instant};
+
 use crate::math::Vec2;
 use crate::Context;
 
+/// How far the mouse can move between the clicks of a double click, in pixels.
+const DOUBLE_CLICK_DISTANCE: f32 = 4.0;
+
 /// A button on a mouse.
 ///
 /// # Serde
@@ -42,6 +47,30 @@ pub fn is_mouse_button_released(ctx: &Context, button: MouseButton) -> bool {
     ctx.input.mouse_buttons_released.contains(&button)
 }
 
+/// Returns true if the specified mouse button was double clicked since the last update.
+///
+/// A double click is registered when a button is pressed twice in quick succession (as
+/// configured by [`set_double_click_time`]) without the mouse moving more than a few pixels.
+/// The second press will also be reported by [`is_mouse_button_pressed`], so if you need to
+/// handle single and double clicks differently, check for the double click first.
+pub fn is_mouse_button_double_clicked(ctx: &Context, button: MouseButton) -> bool {
+    ctx.input.mouse_buttons_double_clicked.contains(&button)
+}
+
+/// Returns the maximum amount of time that can pass between two clicks for them to be
+/// registered as a double click.
+pub fn get_double_click_time(ctx: &Context) -> Duration {
+    ctx.input.double_click_time
+}
+
+/// Sets the maximum amount of time that can pass between two clicks for them to be
+/// registered as a double click.
+///
+/// Defaults to 500 milliseconds.
+pub fn set_double_click_time(ctx: &mut Context, double_click_time: Duration) {
+    ctx.input.double_click_time = double_click_time;
+}
+
 /// Returns true if the user scrolled up since the last update.
 pub fn is_mouse_scrolled_up(ctx: &Context) -> bool {
     get_mouse_wheel_movement(ctx).y > 0
@@ -83,6 +112,17 @@ pub(crate) fn set_mouse_button_down(ctx: &mut Context, btn: MouseButton) -> bool
 
     if was_up {
         ctx.input.mouse_buttons_pressed.insert(btn);
+
+        let is_double_click = ctx.input.click_tracker.register(
+            btn,
+            Instant::now(),
+            ctx.input.mouse_position,
+            ctx.input.double_click_time,
+        );
+
+        if is_double_click {
+            ctx.input.mouse_buttons_double_clicked.insert(btn);
+        }
     }
 
     was_up
@@ -105,3 +145,102 @@ pub(crate) fn set_mouse_position(ctx: &mut Context, position: Vec2<f32>) {
 pub(crate) fn apply_mouse_wheel_movement(ctx: &mut Context, wheel_movement: Vec2<i32>) {
     ctx.input.mouse_wheel_movement += wheel_movement;
 }
+
+/// Keeps track of the last mouse click, so that double clicks can be detected.
+#[derive(Debug, Default)]
+pub(crate) struct ClickTracker {
+    last_click: Option<(MouseButton, Instant, Vec2<f32>)>,
+}
+
+impl ClickTracker {
+    /// Registers a click, returning whether or not it completes a double click.
+    fn register(
+        &mut self,
+        button: MouseButton,
+        time: Instant,
+        position: Vec2<f32>,
+        max_interval: Duration,
+    ) -> bool {
+        let is_double_click = matches!(
+            self.last_click,
+            Some((last_button, last_time, last_position))
+                if last_button == button
+                    && time.saturating_duration_since(last_time) <= max_interval
+                    && last_position.distance(position) <= DOUBLE_CLICK_DISTANCE
+        );
+
+        // A third click shouldn't count as a second double click, so the tracker
+        // starts over once a double click has been registered.
+        self.last_click = if is_double_click {
+            None
+        } else {
+            Some((button, time, position))
+        };
+
+        is_double_click
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(500);
+
+    #[test]
+    fn double_click_within_interval() {
+        let mut tracker = ClickTracker::default();
+        let start = Instant::now();
+
+        assert!(!tracker.register(MouseButton::Left, start, Vec2::zero(), INTERVAL));
+        assert!(tracker.register(
+            MouseButton::Left,
+            start + Duration::from_millis(200),
+            Vec2::zero(),
+            INTERVAL
+        ));
+    }
+
+    #[test]
+    fn slow_or_distant_clicks_are_not_double_clicks() {
+        let mut tracker = ClickTracker::default();
+        let start = Instant::now();
+
+        tracker.register(MouseButton::Left, start, Vec2::zero(), INTERVAL);
+
+        assert!(!tracker.register(
+            MouseButton::Left,
+            start + Duration::from_millis(600),
+            Vec2::zero(),
+            INTERVAL
+        ));
+
+        assert!(!tracker.register(
+            MouseButton::Left,
+            start + Duration::from_millis(700),
+            Vec2::new(50.0, 0.0),
+            INTERVAL
+        ));
+    }
+
+    #[test]
+    fn different_buttons_are_not_double_clicks() {
+        let mut tracker = ClickTracker::default();
+        let start = Instant::now();
+
+        tracker.register(MouseButton::Left, start, Vec2::zero(), INTERVAL);
+
+        assert!(!tracker.register(MouseButton::Right, start, Vec2::zero(), INTERVAL));
+    }
+
+    #[test]
+    fn triple_click_is_one_double_click() {
+        let mut tracker = ClickTracker::default();
+        let start = Instant::now();
+
+        tracker.register(MouseButton::Left, start, Vec2::zero(), INTERVAL);
+        tracker.register(MouseButton::Left, start, Vec2::zero(), INTERVAL);
+
+        assert!(!tracker.register(MouseButton::Left, start, Vec2::zero(), INTERVAL));
+    }
+}
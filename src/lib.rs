@@ -74,6 +74,6 @@ mod platform;
 pub mod time;
 pub mod window;
 
-pub use crate::context::{Context, ContextBuilder};
+pub use crate::context::{Context, ContextBuilder, Settings};
 pub use crate::error::{Result, TetraError};
-pub use crate::lifecycle::{Event, State};
+pub use crate::lifecycle::{Event, EventMode, State};
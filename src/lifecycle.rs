@@ -1,6 +1,7 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::input::{GamepadAxis, GamepadButton, GamepadStick, Key, MouseButton};
+use crate::input::{GamepadAxis, GamepadButton, GamepadStick, JoystickHat, Key, MouseButton};
 use crate::math::Vec2;
 use crate::{Context, TetraError};
 
@@ -32,6 +33,12 @@ pub trait State<E = TetraError> {
     }
 
     /// Called when it is time for the game to be drawn.
+    ///
+    /// When using a fixed timestep, this may be called more or less often than
+    /// [`update`](State::update). Use
+    /// [`time::get_blend_factor`](crate::time::get_blend_factor) to interpolate between
+    /// updates, and [`time::get_delta_time`](crate::time::get_delta_time) to get the real
+    /// amount of time that has passed since the last frame.
     fn draw(&mut self, ctx: &mut Context) -> Result<(), E> {
         Ok(())
     }
@@ -40,6 +47,23 @@ pub trait State<E = TetraError> {
     fn event(&mut self, ctx: &mut Context, event: Event) -> Result<(), E> {
         Ok(())
     }
+
+    /// Called once when the game loop has stopped running, before
+    /// [`Context::run`](crate::Context::run) returns.
+    ///
+    /// This can be used to run cleanup code, such as saving the player's progress.
+    ///
+    /// This will be called regardless of how the game loop stopped - including when one of
+    /// the other `State` methods returned an error. In that case, the original error will
+    /// be returned from `run` once this method has completed, and any error returned from
+    /// this method will be discarded.
+    ///
+    /// The only exception is if the game was stopped via
+    /// [`window::quit_immediately`](crate::window::quit_immediately), in which case this
+    /// method will not be called.
+    fn on_exit(&mut self, ctx: &mut Context) -> Result<(), E> {
+        Ok(())
+    }
 }
 
 /// Events that can occur while the game is running.
@@ -53,13 +77,40 @@ pub trait State<E = TetraError> {
 #[non_exhaustive]
 #[derive(Debug, Clone)]
 pub enum Event {
+    /// The user has requested that the game be closed (e.g. by clicking the window's
+    /// close button).
+    ///
+    /// By default, the game will stop running once this event has been handled. If
+    /// [`ContextBuilder::quit_on_close`](crate::ContextBuilder::quit_on_close) is
+    /// disabled, the game will keep running instead, allowing you to decide whether
+    /// or not to call [`window::quit`](crate::window::quit).
+    QuitRequested,
+
     /// The game window was resized.
+    ///
+    /// By the time this event is received, the viewport and projection used for drawing
+    /// to the screen will already have been updated, so functions such as
+    /// [`window::get_size`](crate::window::get_size) and
+    /// [`graphics::get_viewport`](crate::graphics::get_viewport) will return values that
+    /// are consistent with this event.
     Resized {
-        /// The new width of the game window.
+        /// The new width of the game window, in logical pixels.
         width: i32,
 
-        /// The new height of the game window.
+        /// The new height of the game window, in logical pixels.
         height: i32,
+
+        /// The new width of the game window's drawable area, in physical pixels.
+        ///
+        /// This will only differ from `width` on high-DPI displays, if
+        /// [`ContextBuilder::high_dpi`](crate::ContextBuilder::high_dpi) is enabled.
+        physical_width: i32,
+
+        /// The new height of the game window's drawable area, in physical pixels.
+        ///
+        /// This will only differ from `height` on high-DPI displays, if
+        /// [`ContextBuilder::high_dpi`](crate::ContextBuilder::high_dpi) is enabled.
+        physical_height: i32,
     },
 
     /// The game window was restored to normal size and position by the user, either by
@@ -85,6 +136,11 @@ pub enum Event {
     },
 
     /// A key on the keyboard was released.
+    ///
+    /// This event is always fired as soon as the key is released. If the key was pressed
+    /// since the last update, input polling functions such as
+    /// [`input::is_key_down`](crate::input::is_key_down) will continue to report it as down
+    /// until after the next update, so that the press is not missed.
     KeyReleased {
         /// The key that was released.
         key: Key,
@@ -181,6 +237,64 @@ pub enum Event {
         position: Vec2<f32>,
     },
 
+    /// A joystick device was connected to the system.
+    ///
+    /// Joysticks are not opened automatically - pass the device index to
+    /// [`input::open_joystick`](crate::input::open_joystick) if you want to use it.
+    /// This event will also be fired for devices that are supported by the gamepad API.
+    JoystickAdded {
+        /// The device index of the joystick.
+        device_index: u32,
+    },
+
+    /// An open joystick was removed from the system.
+    JoystickRemoved {
+        /// The ID of the joystick that was removed.
+        id: usize,
+    },
+
+    /// A button on an open joystick was pressed.
+    JoystickButtonPressed {
+        /// The ID of the joystick.
+        id: usize,
+
+        /// The index of the button that was pressed.
+        button: u32,
+    },
+
+    /// A button on an open joystick was released.
+    JoystickButtonReleased {
+        /// The ID of the joystick.
+        id: usize,
+
+        /// The index of the button that was released.
+        button: u32,
+    },
+
+    /// An axis on an open joystick was moved.
+    JoystickAxisMoved {
+        /// The ID of the joystick.
+        id: usize,
+
+        /// The index of the axis that was moved.
+        axis: u32,
+
+        /// The new position of the axis.
+        position: f32,
+    },
+
+    /// A hat switch on an open joystick was moved.
+    JoystickHatMoved {
+        /// The ID of the joystick.
+        id: usize,
+
+        /// The index of the hat switch that was moved.
+        hat: u32,
+
+        /// The new position of the hat switch.
+        position: JoystickHat,
+    },
+
     /// The user typed some text.
     TextInput {
         /// The text that was typed by the user.
@@ -200,3 +314,42 @@ pub enum Event {
         path: PathBuf,
     },
 }
+
+/// The different ways that the game loop can wait for events.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum EventMode {
+    /// The game loop will check for new events once per frame, and then carry on running
+    /// immediately, regardless of whether any events occurred.
+    ///
+    /// This is the best choice for most games, as it allows the game to update and render
+    /// as fast as the timestep and vsync settings allow.
+    ///
+    /// This mode is currently the default.
+    Poll,
+
+    /// The game loop will sleep until an event occurs, or until the specified amount of
+    /// time passes, before running the rest of the frame.
+    ///
+    /// This can greatly reduce CPU usage (and battery drain) for applications that spend
+    /// most of their time idle, such as tools and editors, as they will only update and
+    /// render when there is something to respond to.
+    ///
+    /// Time will still be measured in real terms while waiting, so when using a fixed
+    /// timestep, the time spent asleep will be added to the accumulator for the frame
+    /// that wakes up. If the game is woken by an event before a full tick has passed,
+    /// it will stop waiting (and poll for events instead) until the next update has run,
+    /// so that input is never left waiting for the timeout. Note that the accumulator is
+    /// capped, so not every missed update will be run if the timeout is longer than a
+    /// few ticks.
+    WaitWithTimeout(Duration),
+}
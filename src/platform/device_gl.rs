@@ -11,8 +11,8 @@ use crate::graphics::{
     StencilState, StencilTest,
 };
 use crate::graphics::{
-    BlendFactor, BlendOperation, BlendState, Color, FilterMode, GraphicsDeviceInfo, StencilAction,
-    TextureFormat,
+    BlendFactor, BlendOperation, BlendState, Color, FilterMode, GraphicsDeviceInfo, PolygonMode,
+    StencilAction, TextureFormat, WrapMode,
 };
 use crate::math::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
 
@@ -35,6 +35,8 @@ struct GraphicsState {
     current_read_framebuffer: Cell<Option<FramebufferId>>,
     current_draw_framebuffer: Cell<Option<FramebufferId>>,
     current_renderbuffer: Cell<Option<RenderbufferId>>,
+    current_cull_face: Cell<bool>,
+    current_front_face: Cell<VertexWinding>,
 
     vertex_array: VertexArrayId,
     resolve_framebuffer: FramebufferId,
@@ -88,6 +90,10 @@ impl GraphicsDevice {
                 current_draw_framebuffer: Cell::new(None),
                 current_renderbuffer: Cell::new(None),
 
+                // These match the initial state that was set above/the OpenGL defaults.
+                current_cull_face: Cell::new(true),
+                current_front_face: Cell::new(VertexWinding::CounterClockwise),
+
                 vertex_array,
                 resolve_framebuffer,
 
@@ -125,12 +131,23 @@ impl GraphicsDevice {
     }
 
     pub fn front_face(&mut self, front_face: VertexWinding) {
+        // This gets called on every flush, so it's worth skipping redundant calls.
+        if self.state.current_front_face.get() == front_face {
+            return;
+        }
+
         unsafe {
             self.state.gl.front_face(front_face.to_gl_enum());
         }
+
+        self.state.current_front_face.set(front_face);
     }
 
     pub fn cull_face(&mut self, cull_face: bool) {
+        if self.state.current_cull_face.get() == cull_face {
+            return;
+        }
+
         unsafe {
             if cull_face {
                 self.state.gl.enable(glow::CULL_FACE);
@@ -138,6 +155,8 @@ impl GraphicsDevice {
                 self.state.gl.disable(glow::CULL_FACE);
             }
         }
+
+        self.state.current_cull_face.set(cull_face);
     }
 
     pub fn scissor(&mut self, x: i32, y: i32, width: i32, height: i32) {
@@ -189,6 +208,22 @@ impl GraphicsDevice {
         }
     }
 
+    // Returns `false` if polygon modes are not supported by the current context.
+    pub fn set_polygon_mode(&mut self, mode: PolygonMode) -> bool {
+        // glPolygonMode is not available in OpenGL ES.
+        if self.state.gl.version().is_embedded {
+            return false;
+        }
+
+        unsafe {
+            self.state
+                .gl
+                .polygon_mode(glow::FRONT_AND_BACK, mode.to_gl_enum());
+        }
+
+        true
+    }
+
     pub fn new_vertex_buffer(
         &mut self,
         count: usize,
@@ -614,7 +649,8 @@ impl GraphicsDevice {
         format: TextureFormat,
         filter_mode: FilterMode,
     ) -> Result<RawTexture> {
-        // TODO: I don't think we need mipmaps?
+        // Mipmaps are disabled by default, as most 2D games don't need them - they can be
+        // enabled later via `generate_mipmaps`.
         unsafe {
             let id = self
                 .state
@@ -762,14 +798,57 @@ impl GraphicsDevice {
         buffer
     }
 
-    pub fn set_texture_filter_mode(&mut self, texture: &RawTexture, filter_mode: FilterMode) {
+    pub fn get_screen_data(&mut self, width: i32, height: i32) -> Vec<u8> {
+        let stride = width as usize * 4;
+        let mut buffer = vec![0; stride * height as usize];
+
+        unsafe {
+            let previous_read = self.state.current_read_framebuffer.get();
+
+            self.bind_read_framebuffer(None);
+
+            self.state.gl.read_pixels(
+                0,
+                0,
+                width,
+                height,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                PixelPackData::Slice(&mut buffer),
+            );
+
+            self.bind_read_framebuffer(previous_read);
+        }
+
+        // OpenGL returns the rows from bottom to top.
+        let mut flipped = Vec::with_capacity(buffer.len());
+
+        for row in buffer.chunks_exact(stride).rev() {
+            flipped.extend_from_slice(row);
+        }
+
+        flipped
+    }
+
+    pub fn set_texture_filter_mode(
+        &mut self,
+        texture: &RawTexture,
+        filter_mode: FilterMode,
+        mipmaps: bool,
+    ) {
         self.bind_default_texture(Some(texture.id));
 
+        let min_filter = if mipmaps {
+            filter_mode.to_gl_mipmap_enum()
+        } else {
+            filter_mode.to_gl_enum()
+        };
+
         unsafe {
             self.state.gl.tex_parameter_i32(
                 glow::TEXTURE_2D,
                 glow::TEXTURE_MIN_FILTER,
-                filter_mode.to_gl_enum() as i32,
+                min_filter as i32,
             );
 
             self.state.gl.tex_parameter_i32(
@@ -780,6 +859,68 @@ impl GraphicsDevice {
         }
     }
 
+    pub fn generate_mipmaps(&mut self, texture: &RawTexture, filter_mode: FilterMode) -> Result {
+        // GL 3.2 supports mipmaps for non-power-of-two textures, and all of our texture
+        // formats are filterable, so the size is the only thing that needs checking.
+        let max_size = unsafe { self.state.gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) };
+
+        if texture.width <= 0
+            || texture.height <= 0
+            || texture.width > max_size
+            || texture.height > max_size
+        {
+            return Err(TetraError::PlatformError(format!(
+                "can't generate mipmaps for a {}x{} texture (sizes must be between 1 and {})",
+                texture.width, texture.height, max_size
+            )));
+        }
+
+        self.bind_default_texture(Some(texture.id));
+
+        unsafe {
+            self.state
+                .gl
+                .tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, 1000);
+
+            self.clear_errors();
+
+            self.state.gl.generate_mipmap(glow::TEXTURE_2D);
+
+            if let Some(e) = self.get_error() {
+                return Err(TetraError::PlatformError(format_gl_error(
+                    "failed to generate mipmaps",
+                    e,
+                )));
+            }
+
+            self.state.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MIN_FILTER,
+                filter_mode.to_gl_mipmap_enum() as i32,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn set_texture_wrap_mode(&mut self, texture: &RawTexture, wrap_mode: WrapMode) {
+        self.bind_default_texture(Some(texture.id));
+
+        unsafe {
+            self.state.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_WRAP_S,
+                wrap_mode.to_gl_enum() as i32,
+            );
+
+            self.state.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_WRAP_T,
+                wrap_mode.to_gl_enum() as i32,
+            );
+        }
+    }
+
     pub fn attach_texture_to_sampler(&mut self, texture: &RawTexture, unit: u32) -> Result {
         self.bind_texture(Some(texture.id), unit)
     }
@@ -1227,6 +1368,24 @@ impl FilterMode {
             FilterMode::Linear => glow::LINEAR,
         }
     }
+
+    fn to_gl_mipmap_enum(self) -> u32 {
+        match self {
+            FilterMode::Nearest => glow::NEAREST_MIPMAP_NEAREST,
+            FilterMode::Linear => glow::LINEAR_MIPMAP_LINEAR,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl WrapMode {
+    fn to_gl_enum(self) -> u32 {
+        match self {
+            WrapMode::Clamp => glow::CLAMP_TO_EDGE,
+            WrapMode::Repeat => glow::REPEAT,
+            WrapMode::MirroredRepeat => glow::MIRRORED_REPEAT,
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -1334,6 +1493,16 @@ impl StencilAction {
     }
 }
 
+#[doc(hidden)]
+impl PolygonMode {
+    fn to_gl_enum(self) -> u32 {
+        match self {
+            PolygonMode::Fill => glow::FILL,
+            PolygonMode::Line => glow::LINE,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct RawVertexBuffer {
     state: Rc<GraphicsState>,
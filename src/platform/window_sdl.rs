@@ -1,4 +1,5 @@
 // TODO: This file is getting way too huge.
+use std::os::raw::c_int;
 use std::path::PathBuf;
 use std::result;
 
@@ -6,6 +7,7 @@ use glow::Context as GlowContext;
 use hashbrown::HashMap;
 use sdl2::controller::{Axis as SdlGamepadAxis, Button as SdlGamepadButton, GameController};
 use sdl2::event::{Event as SdlEvent, WindowEvent};
+use sdl2::joystick::{HatState, Joystick};
 use sdl2::keyboard::{Keycode, Mod, Scancode};
 use sdl2::mouse::{MouseButton as SdlMouseButton, MouseWheelDirection};
 use sdl2::pixels::PixelMasks;
@@ -18,18 +20,20 @@ use sdl2::video::{
 use sdl2::{EventPump, GameControllerSubsystem, JoystickSubsystem, Sdl, VideoSubsystem};
 
 use crate::error::{Result, TetraError};
-use crate::graphics::{self, ImageData};
+use crate::graphics::{self, GlProfile, ImageData};
 use crate::input::{
-    self, GamepadAxis, GamepadButton, GamepadStick, Key, KeyLabel, KeyModifierState, MouseButton,
+    self, GamepadAxis, GamepadButton, GamepadStick, GamepadType, JoystickHat, JoystickState, Key,
+    KeyLabel, KeyModifierState, MouseButton,
 };
 use crate::math::Vec2;
 use crate::window::WindowPosition;
-use crate::{Context, ContextBuilder, Event, State};
+use crate::{Context, ContextBuilder, Event, EventMode, State};
 
 struct SdlController {
     controller: GameController,
     slot: usize,
     supports_rumble: bool,
+    gamepad_type: GamepadType,
 }
 
 pub struct Window {
@@ -39,10 +43,11 @@ pub struct Window {
     event_pump: EventPump,
     video_sys: VideoSubsystem,
     controller_sys: GameControllerSubsystem,
-    _joystick_sys: JoystickSubsystem,
+    joystick_sys: JoystickSubsystem,
     _gl_sys: SdlGlContext,
 
     controllers: HashMap<u32, SdlController>,
+    joysticks: HashMap<u32, Joystick>,
 
     window_visible: bool,
 
@@ -61,8 +66,20 @@ impl Window {
 
         let gl_attr = video_sys.gl_attr();
 
-        gl_attr.set_context_profile(GLProfile::Core);
-        gl_attr.set_context_version(3, 2);
+        let (gl_major, gl_minor) = settings.gl_version;
+
+        if (gl_major, gl_minor) < (3, 2) {
+            return Err(TetraError::PlatformError(format!(
+                "OpenGL {}.{} was requested, but Tetra requires at least OpenGL 3.2",
+                gl_major, gl_minor
+            )));
+        }
+
+        gl_attr.set_context_profile(match settings.gl_profile {
+            GlProfile::Core => GLProfile::Core,
+            GlProfile::Compatibility => GLProfile::Compatibility,
+        });
+        gl_attr.set_context_version(gl_major, gl_minor);
         gl_attr.set_red_size(8);
         gl_attr.set_green_size(8);
         gl_attr.set_blue_size(8);
@@ -149,9 +166,12 @@ impl Window {
                 .map_err(TetraError::FailedToChangeDisplayMode)?;
         }
 
-        let gl_sys = sdl_window
-            .gl_create_context()
-            .map_err(TetraError::PlatformError)?;
+        let gl_sys = sdl_window.gl_create_context().map_err(|e| {
+            TetraError::PlatformError(format!(
+                "failed to create OpenGL {}.{} {:?} context: {}",
+                gl_major, gl_minor, settings.gl_profile, e
+            ))
+        })?;
 
         let gl_ctx = unsafe {
             GlowContext::from_loader_function(|s| video_sys.gl_get_proc_address(s) as *const _)
@@ -170,10 +190,11 @@ impl Window {
             event_pump,
             video_sys,
             controller_sys,
-            _joystick_sys: joystick_sys,
+            joystick_sys,
             _gl_sys: gl_sys,
 
             controllers: HashMap::new(),
+            joysticks: HashMap::new(),
 
             window_visible: false,
 
@@ -199,6 +220,11 @@ impl Window {
         self.sdl_window.raise()
     }
 
+    pub fn is_focused(&self) -> bool {
+        let flags = self.sdl_window.window_flags();
+        flags & sdl2::sys::SDL_WindowFlags::SDL_WINDOW_INPUT_FOCUS as u32 != 0
+    }
+
     pub fn get_window_title(&self) -> &str {
         self.sdl_window.title()
     }
@@ -378,6 +404,20 @@ impl Window {
         self.sdl.mouse().is_cursor_showing()
     }
 
+    pub fn set_text_input_enabled(&mut self, text_input_enabled: bool) {
+        let text_input = self.video_sys.text_input();
+
+        if text_input_enabled {
+            text_input.start();
+        } else {
+            text_input.stop();
+        }
+    }
+
+    pub fn is_text_input_enabled(&self) -> bool {
+        self.video_sys.text_input().is_active()
+    }
+
     pub fn set_mouse_grabbed(&mut self, mouse_grabbed: bool) {
         self.sdl_window.set_grab(mouse_grabbed);
     }
@@ -418,6 +458,17 @@ impl Window {
         self.controllers[&platform_id].controller.name()
     }
 
+    pub fn get_gamepad_type(&self, platform_id: u32) -> GamepadType {
+        self.controllers[&platform_id].gamepad_type
+    }
+
+    pub fn add_gamepad_mappings(&mut self, mappings: &str) -> Result<u32> {
+        self.controller_sys
+            .load_mappings_from_read(&mut mappings.as_bytes())
+            .map(|count| count as u32)
+            .map_err(|e| TetraError::PlatformError(e.to_string()))
+    }
+
     pub fn is_gamepad_vibration_supported(&self, platform_id: u32) -> bool {
         self.controllers
             .get(&platform_id)
@@ -451,6 +502,62 @@ impl Window {
         }
     }
 
+    pub fn get_joystick_device_count(&self) -> Result<u32> {
+        self.joystick_sys
+            .num_joysticks()
+            .map_err(TetraError::PlatformError)
+    }
+
+    pub fn get_joystick_device_name(&self, device_index: u32) -> Result<String> {
+        self.joystick_sys
+            .name_for_index(device_index)
+            .map_err(|e| TetraError::PlatformError(e.to_string()))
+    }
+
+    pub fn open_joystick(&mut self, device_index: u32) -> Result<JoystickState> {
+        let joystick = self
+            .joystick_sys
+            .open(device_index)
+            .map_err(|e| TetraError::PlatformError(e.to_string()))?;
+
+        let mut state = JoystickState::new(
+            joystick.instance_id(),
+            joystick.num_axes(),
+            joystick.num_buttons(),
+            joystick.num_hats(),
+        );
+
+        for axis in 0..joystick.num_axes() {
+            if let Ok(value) = joystick.axis(axis) {
+                state.set_axis_position(axis, map_axis_value(value));
+            }
+        }
+
+        for button in 0..joystick.num_buttons() {
+            if let Ok(true) = joystick.button(button) {
+                state.buttons_down.insert(button);
+            }
+        }
+
+        for hat in 0..joystick.num_hats() {
+            if let Ok(hat_state) = joystick.hat(hat) {
+                state.set_hat_position(hat, hat_state.into());
+            }
+        }
+
+        self.joysticks.insert(joystick.instance_id(), joystick);
+
+        Ok(state)
+    }
+
+    pub fn close_joystick(&mut self, platform_id: u32) {
+        self.joysticks.remove(&platform_id);
+    }
+
+    pub fn get_joystick_name(&self, platform_id: u32) -> String {
+        self.joysticks[&platform_id].name()
+    }
+
     pub fn set_screen_saver_enabled(&self, screen_saver_enabled: bool) {
         if screen_saver_enabled {
             self.video_sys.enable_screen_saver()
@@ -463,6 +570,16 @@ impl Window {
         self.video_sys.is_screen_saver_enabled()
     }
 
+    pub fn get_sdl_version(&self) -> String {
+        // This is the version that was linked at runtime, which may differ from the
+        // version that the bindings were compiled against.
+        format!(
+            "{} ({})",
+            sdl2::version::version(),
+            sdl2::version::revision()
+        )
+    }
+
     pub fn set_key_repeat_enabled(&mut self, key_repeat: bool) {
         self.key_repeat = key_repeat;
     }
@@ -484,19 +601,64 @@ impl Window {
     }
 }
 
-pub fn handle_events<S, E>(ctx: &mut Context, state: &mut S) -> result::Result<(), E>
+/// Returns whether or not any events were received.
+///
+/// If `allow_wait` is false, the event mode is ignored and the queue is only polled.
+pub fn handle_events<S, E>(
+    ctx: &mut Context,
+    state: &mut S,
+    allow_wait: bool,
+) -> result::Result<bool, E>
 where
     S: State<E>,
     E: From<TetraError>,
 {
-    while let Some(event) = ctx.window.event_pump.poll_event() {
+    let mut next_event = match ctx.event_mode {
+        EventMode::WaitWithTimeout(_) if !allow_wait => ctx.window.event_pump.poll_event(),
+        EventMode::Poll => ctx.window.event_pump.poll_event(),
+        EventMode::WaitWithTimeout(timeout) => {
+            let timeout = timeout.as_millis().min(u32::MAX as u128) as u32;
+            ctx.window.event_pump.wait_event_timeout(timeout)
+        }
+    };
+
+    let received_events = next_event.is_some();
+
+    while let Some(event) = next_event.take() {
+        if ctx.quit_immediately {
+            break;
+        }
+
         match event {
-            SdlEvent::Quit { .. } => ctx.running = false, // TODO: Add a way to override this
+            SdlEvent::Quit { .. } => {
+                state.event(ctx, Event::QuitRequested)?;
+
+                if ctx.quit_on_close {
+                    ctx.running = false;
+                }
+            }
 
             SdlEvent::Window { win_event, .. } => match win_event {
                 WindowEvent::SizeChanged(width, height) => {
+                    // The viewport needs to be updated before the event is fired, so that
+                    // any size queries made by the event handler are consistent.
                     graphics::set_viewport_size(ctx);
-                    state.event(ctx, Event::Resized { width, height })?;
+
+                    if let Some(scaler) = &mut ctx.screen_scaler {
+                        scaler.set_outer_size(width, height);
+                    }
+
+                    let (physical_width, physical_height) = ctx.window.get_physical_size();
+
+                    state.event(
+                        ctx,
+                        Event::Resized {
+                            width,
+                            height,
+                            physical_width,
+                            physical_height,
+                        },
+                    )?;
                 }
 
                 WindowEvent::Restored => {
@@ -552,8 +714,6 @@ where
                 input::set_key_modifier_state(ctx, from_sdl_keymod(keymod));
 
                 if let Some(key) = from_sdl_scancode(scancode) {
-                    // TODO: This can cause some inputs to be missed at low tick rates.
-                    // Could consider buffering input releases like Otter2D does?
                     input::set_key_up(ctx, key);
                     state.event(ctx, Event::KeyReleased { key })?;
                 }
@@ -576,8 +736,15 @@ where
             SdlEvent::MouseMotion {
                 x, y, xrel, yrel, ..
             } => {
-                let position = Vec2::new(x as f32, y as f32);
-                let delta = Vec2::new(xrel as f32, yrel as f32);
+                let mut position = Vec2::new(x as f32, y as f32);
+                let mut delta = Vec2::new(xrel as f32, yrel as f32);
+
+                // If the screen is being scaled automatically, the game should only ever
+                // see logical co-ordinates.
+                if let Some(scaler) = &ctx.screen_scaler {
+                    position = scaler.project(position);
+                    delta /= scaler.scale_factor();
+                }
 
                 input::set_mouse_position(ctx, position);
                 state.event(ctx, Event::MouseMoved { position, delta })?;
@@ -609,6 +776,88 @@ where
                 )?;
             }
 
+            SdlEvent::JoyDeviceAdded { which, .. } => {
+                state.event(
+                    ctx,
+                    Event::JoystickAdded {
+                        device_index: which,
+                    },
+                )?;
+            }
+
+            SdlEvent::JoyDeviceRemoved { which, .. } => {
+                if let Some(id) = input::find_joystick(ctx, which) {
+                    ctx.window.close_joystick(which);
+                    input::remove_joystick(ctx, id);
+
+                    state.event(ctx, Event::JoystickRemoved { id })?;
+                }
+            }
+
+            SdlEvent::JoyButtonDown {
+                which, button_idx, ..
+            } => {
+                if let Some(id) = input::find_joystick(ctx, which) {
+                    if let Some(joystick) = input::get_joystick_mut(ctx, id) {
+                        let button = u32::from(button_idx);
+
+                        if joystick.set_button_down(button) {
+                            state.event(ctx, Event::JoystickButtonPressed { id, button })?;
+                        }
+                    }
+                }
+            }
+
+            SdlEvent::JoyButtonUp {
+                which, button_idx, ..
+            } => {
+                if let Some(id) = input::find_joystick(ctx, which) {
+                    if let Some(joystick) = input::get_joystick_mut(ctx, id) {
+                        let button = u32::from(button_idx);
+
+                        if joystick.set_button_up(button) {
+                            state.event(ctx, Event::JoystickButtonReleased { id, button })?;
+                        }
+                    }
+                }
+            }
+
+            SdlEvent::JoyAxisMotion {
+                which,
+                axis_idx,
+                value,
+                ..
+            } => {
+                if let Some(id) = input::find_joystick(ctx, which) {
+                    if let Some(joystick) = input::get_joystick_mut(ctx, id) {
+                        let axis = u32::from(axis_idx);
+                        let position = map_axis_value(value);
+
+                        joystick.set_axis_position(axis, position);
+
+                        state.event(ctx, Event::JoystickAxisMoved { id, axis, position })?;
+                    }
+                }
+            }
+
+            SdlEvent::JoyHatMotion {
+                which,
+                hat_idx,
+                state: hat_state,
+                ..
+            } => {
+                if let Some(id) = input::find_joystick(ctx, which) {
+                    if let Some(joystick) = input::get_joystick_mut(ctx, id) {
+                        let hat = u32::from(hat_idx);
+                        let position = hat_state.into();
+
+                        joystick.set_hat_position(hat, position);
+
+                        state.event(ctx, Event::JoystickHatMoved { id, hat, position })?;
+                    }
+                }
+            }
+
             SdlEvent::ControllerDeviceAdded { which, .. } => {
                 let mut controller = ctx
                     .window
@@ -621,12 +870,20 @@ where
 
                 let supports_rumble = controller.set_rumble(0, 0, 0).is_ok();
 
+                // SAFETY: This function only reads SDL's internal joystick list, and returns
+                // SDL_CONTROLLER_TYPE_UNKNOWN if the index is out of range. The result is
+                // received as a plain integer, rather than via the enum in sdl2-sys, as newer
+                // versions of SDL can return values that the bindings don't know about.
+                let gamepad_type =
+                    into_gamepad_type(unsafe { SDL_GameControllerTypeForIndex(which as c_int) });
+
                 ctx.window.controllers.insert(
                     id,
                     SdlController {
                         controller,
                         slot,
                         supports_rumble,
+                        gamepad_type,
                     },
                 );
 
@@ -676,11 +933,7 @@ where
                     if let Some(pad) = input::get_gamepad_mut(ctx, slot) {
                         let axis = axis.into();
 
-                        let mapped_value = if value > 0 {
-                            f32::from(value) / 32767.0
-                        } else {
-                            f32::from(value) / 32768.0
-                        };
+                        let mapped_value = map_axis_value(value);
 
                         pad.set_axis_position(axis, mapped_value);
 
@@ -747,9 +1000,11 @@ where
 
             _ => {}
         }
+
+        next_event = ctx.window.event_pump.poll_event();
     }
 
-    Ok(())
+    Ok(received_events)
 }
 
 fn into_mouse_button(button: SdlMouseButton) -> Option<MouseButton> {
@@ -1023,6 +1278,52 @@ impl From<GamepadAxis> for SdlGamepadAxis {
 }
 
 #[doc(hidden)]
+fn map_axis_value(value: i16) -> f32 {
+    if value > 0 {
+        f32::from(value) / 32767.0
+    } else {
+        f32::from(value) / 32768.0
+    }
+}
+
+impl From<HatState> for JoystickHat {
+    fn from(state: HatState) -> JoystickHat {
+        match state {
+            HatState::Centered => JoystickHat::Centered,
+            HatState::Up => JoystickHat::Up,
+            HatState::RightUp => JoystickHat::UpRight,
+            HatState::Right => JoystickHat::Right,
+            HatState::RightDown => JoystickHat::DownRight,
+            HatState::Down => JoystickHat::Down,
+            HatState::LeftDown => JoystickHat::DownLeft,
+            HatState::Left => JoystickHat::Left,
+            HatState::LeftUp => JoystickHat::UpLeft,
+        }
+    }
+}
+
+extern "C" {
+    // Declared locally instead of using sdl2-sys, as its binding returns an enum that only
+    // covers the controller types that existed in SDL 2.0.14. Later versions add more
+    // (e.g. Stadia, Luna and Joy-Con pads), and receiving those as a Rust enum would be
+    // undefined behaviour.
+    fn SDL_GameControllerTypeForIndex(joystick_index: c_int) -> c_int;
+}
+
+fn into_gamepad_type(gamepad_type: c_int) -> GamepadType {
+    // These values come from the `SDL_GameControllerType` enum in SDL_gamecontroller.h.
+    match gamepad_type {
+        1 => GamepadType::Xbox360,
+        2 => GamepadType::XboxOne,
+        3 => GamepadType::PlayStation3,
+        4 => GamepadType::PlayStation4,
+        5 => GamepadType::NintendoSwitchPro,
+        6 => GamepadType::Virtual,
+        7 => GamepadType::PlayStation5,
+        _ => GamepadType::Unknown,
+    }
+}
+
 impl From<SdlGamepadAxis> for GamepadAxis {
     fn from(axis: SdlGamepadAxis) -> GamepadAxis {
         match axis {
@@ -4,6 +4,7 @@ use std::collections::VecDeque;
 
 use std::time::Duration;
 
+use crate::graphics;
 use crate::Context;
 
 /// The different timestep modes that a game can have.
@@ -69,6 +70,26 @@ impl FpsTracker {
     fn get_fps(&self) -> f64 {
         1.0 / (self.buffer.iter().sum::<f64>() / self.buffer.len() as f64)
     }
+
+    fn get_latest(&self) -> f64 {
+        self.buffer.back().copied().unwrap_or(0.0)
+    }
+
+    fn get_average(&self) -> f64 {
+        if self.buffer.is_empty() {
+            0.0
+        } else {
+            self.buffer.iter().sum::<f64>() / self.buffer.len() as f64
+        }
+    }
+
+    fn get_min(&self) -> f64 {
+        self.buffer.iter().copied().reduce(f64::min).unwrap_or(0.0)
+    }
+
+    fn get_max(&self) -> f64 {
+        self.buffer.iter().copied().reduce(f64::max).unwrap_or(0.0)
+    }
 }
 
 pub(crate) struct TimeContext {
@@ -77,6 +98,8 @@ pub(crate) struct TimeContext {
     pub(crate) tick_rate: Option<Duration>,
     pub(crate) delta_time: Duration,
     pub(crate) accumulator: Duration,
+    pub(crate) elapsed_time: Duration,
+    pub(crate) updates: u32,
 }
 
 impl TimeContext {
@@ -97,6 +120,8 @@ impl TimeContext {
             tick_rate,
             delta_time: Duration::from_secs(0),
             accumulator: Duration::from_secs(0),
+            elapsed_time: Duration::from_secs(0),
+            updates: 0,
         }
     }
 }
@@ -104,6 +129,8 @@ impl TimeContext {
 pub(crate) fn reset(ctx: &mut Context) {
     ctx.time.delta_time = Duration::from_secs(0);
     ctx.time.accumulator = Duration::from_secs(0);
+    ctx.time.elapsed_time = Duration::from_secs(0);
+    ctx.time.updates = 0;
 }
 
 /// Returns the amount of time that has passed since the last update or draw.
@@ -111,15 +138,28 @@ pub(crate) fn reset(ctx: &mut Context) {
 /// This can be used to integrate the amount of time that has passed into your game's
 /// calculations. For example, if you wanted to move a [`Vec2`](crate::math::Vec2) 32
 /// units to the right per second, you could do
-/// `foo.y += 32.0 * time::get_delta_time(ctx).as_secs_f32()`.
+/// `foo.x += 32.0 * time::get_delta_time(ctx).as_secs_f32()`.
 ///
 /// When using a fixed time step, calling this function during an update will always
 /// return the configured update rate. This is to prevent floating point error/non-determinism
 /// from creeping into your game's calculations!
+///
+/// Calling this function during a draw will always return the real amount of time that
+/// passed since the previous frame, regardless of the timestep. Note that this is *not*
+/// the same thing as the interpolation value between updates - if you want to smooth
+/// out your rendering when using a fixed time step, use [`get_blend_factor`] instead.
 pub fn get_delta_time(ctx: &Context) -> Duration {
     ctx.time.delta_time
 }
 
+/// Returns the amount of time that has passed since the game started running.
+///
+/// This is measured from when [`Context::run`](crate::Context::run) was called, and is updated
+/// once per frame.
+pub fn get_elapsed_time(ctx: &Context) -> Duration {
+    ctx.time.elapsed_time
+}
+
 /// Returns the amount of time that has accumulated between updates.
 ///
 /// When using a fixed time step, as time passes, this value will increase;
@@ -136,7 +176,13 @@ pub fn get_accumulator(ctx: &Context) -> Duration {
 /// For example, if the value is 0.01, an update just happened; if the value is 0.99,
 /// an update is about to happen.
 ///
-/// This can be used to interpolate when rendering.
+/// This can be used to interpolate when rendering - for example, if you store the position
+/// of an entity before and after each update, you can calculate where to draw it via
+/// `previous.lerp(current, time::get_blend_factor(ctx))`. Note that this value is a ratio,
+/// not an amount of time - if you need to know how long the last frame took, use
+/// [`get_delta_time`] instead.
+///
+/// When using a variable time step, this function always returns `0.0`.
 ///
 /// This function returns an [`f32`], which is usually what you want when blending - however,
 /// if you need a more precise representation of the blend factor, you can call
@@ -154,7 +200,9 @@ pub fn get_blend_factor(ctx: &Context) -> f32 {
 /// For example, if the value is 0.01, an update just happened; if the value is 0.99,
 /// an update is about to happen.
 ///
-/// This can be used to interpolate when rendering.
+/// This can be used to interpolate when rendering. See [`get_blend_factor`] for more details.
+///
+/// When using a variable time step, this function always returns `0.0`.
 ///
 /// This function returns an [`f64`], which is a very precise representation of the blend factor,
 /// but often difficult to use in game logic without casting. If you need an [`f32`], call
@@ -191,3 +239,59 @@ pub fn set_timestep(ctx: &mut Context, timestep: Timestep) {
 pub fn get_fps(ctx: &Context) -> f64 {
     ctx.time.fps_tracker.get_fps()
 }
+
+/// Statistics about the performance of the game loop.
+///
+/// This can be retrieved via [`get_frame_stats`], and is mainly intended for use in
+/// debug overlays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameStats {
+    /// The amount of time that the last frame took.
+    pub frame_time: Duration,
+
+    /// The average frame time, over the last 200 frames.
+    pub average_frame_time: Duration,
+
+    /// The shortest frame time, over the last 200 frames.
+    pub min_frame_time: Duration,
+
+    /// The longest frame time, over the last 200 frames.
+    pub max_frame_time: Duration,
+
+    /// The number of updates that have run during the current frame.
+    ///
+    /// When using a fixed timestep, this may be zero (if the game is rendering faster than
+    /// it's updating) or more than one (if the game is catching up after a slow frame).
+    /// When using a variable timestep, this will always be one once the frame's update
+    /// has run.
+    pub updates: u32,
+
+    /// The number of draw calls that were submitted to the graphics hardware during the
+    /// last completed frame.
+    pub draw_calls: usize,
+
+    /// The number of quads that were drawn via the sprite batch during the last completed
+    /// frame. This includes textures, text and primitive shapes, but not meshes.
+    pub batched_quads: usize,
+}
+
+/// Returns statistics about the performance of the game loop.
+///
+/// The frame timings are tracked over the same window as [`get_fps`]. The rendering
+/// statistics are collected when the screen is [presented](crate::graphics::present),
+/// so they will always describe the last completed frame, rather than the one that is
+/// currently being drawn.
+pub fn get_frame_stats(ctx: &Context) -> FrameStats {
+    let render_stats = graphics::get_last_render_stats(ctx);
+    let tracker = &ctx.time.fps_tracker;
+
+    FrameStats {
+        frame_time: Duration::from_secs_f64(tracker.get_latest()),
+        average_frame_time: Duration::from_secs_f64(tracker.get_average()),
+        min_frame_time: Duration::from_secs_f64(tracker.get_min()),
+        max_frame_time: Duration::from_secs_f64(tracker.get_max()),
+        updates: ctx.time.updates,
+        draw_calls: render_stats.draw_calls,
+        batched_quads: render_stats.batched_quads,
+    }
+}
@@ -1,15 +1,33 @@
 //! Functions and types relating to the game window, and the environment it is running in.
 
-use crate::{graphics::ImageData, Context, Result};
+use crate::{graphics::ImageData, Context, EventMode, Result};
 
 /// Quits the game, if it is currently running.
 ///
-/// Note that quitting the game does not take effect until the end of the current
-/// cycle of the game loop. This will probably change later.
+/// This is a graceful shutdown - the current cycle of the game loop will be allowed to
+/// finish (including any remaining updates, the call to [`State::draw`](crate::State::draw)
+/// and presenting to the screen), and then [`State::on_exit`](crate::State::on_exit) will
+/// be called before [`Context::run`](crate::Context::run) returns.
+///
+/// If you want to stop the game without doing any further work, use [`quit_immediately`]
+/// instead.
 pub fn quit(ctx: &mut Context) {
     ctx.running = false;
 }
 
+/// Quits the game as soon as possible, if it is currently running.
+///
+/// Unlike [`quit`], the rest of the current cycle of the game loop will be skipped - once the
+/// current [`State`](crate::State) method returns, no further updates will run, nothing will
+/// be drawn or presented, and [`State::on_exit`](crate::State::on_exit) will **not** be called.
+///
+/// Code after this call in the current method will still run, as the game loop can only
+/// stop once control is returned to it.
+pub fn quit_immediately(ctx: &mut Context) {
+    ctx.running = false;
+    ctx.quit_immediately = true;
+}
+
 /// Maximizes the window.
 pub fn maximize(ctx: &mut Context) {
     ctx.window.maximize();
@@ -34,6 +52,16 @@ pub fn focus(ctx: &mut Context) {
     ctx.window.focus();
 }
 
+/// Returns true if the window currently has input focus.
+///
+/// This can be used to pause the game when the player switches to another application.
+/// If you want to be notified when this changes, handle
+/// [`Event::FocusGained`](crate::Event::FocusGained) and
+/// [`Event::FocusLost`](crate::Event::FocusLost) instead.
+pub fn is_focused(ctx: &Context) -> bool {
+    ctx.window.is_focused()
+}
+
 /// Gets the current title of the window.
 pub fn get_title(ctx: &Context) -> &str {
     ctx.window.get_window_title()
@@ -413,6 +441,28 @@ pub fn get_current_monitor_size(ctx: &Context) -> Result<(i32, i32)> {
     ctx.window.get_monitor_size(monitor_index)
 }
 
+/// Returns the version of SDL that the game is running with.
+///
+/// This is the version of the library that was loaded at runtime, which may differ from the
+/// version that Tetra was built against if SDL is being linked dynamically. This may be
+/// useful for debugging/logging purposes, alongside
+/// [`graphics::get_device_info`](crate::graphics::get_device_info).
+pub fn get_sdl_version(ctx: &Context) -> String {
+    ctx.window.get_sdl_version()
+}
+
+/// Sets how the game loop should wait for events.
+///
+/// See [`EventMode`] for the available options.
+pub fn set_event_mode(ctx: &mut Context, event_mode: EventMode) {
+    ctx.event_mode = event_mode;
+}
+
+/// Returns how the game loop is currently waiting for events.
+pub fn get_event_mode(ctx: &Context) -> EventMode {
+    ctx.event_mode
+}
+
 /// Sets whether or not the user's screen saver can be displayed while the game is running.
 pub fn set_screen_saver_enabled(ctx: &Context, screen_saver_enabled: bool) {
     ctx.window.set_screen_saver_enabled(screen_saver_enabled);